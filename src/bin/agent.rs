@@ -1,15 +1,19 @@
 use log::info;
 
 use ethers::signers::{LocalWallet, Signer};
-use hyperliquid_rust_sdk::{BaseUrl, ClientLimit, ClientOrder, ClientOrderRequest, ExchangeClient};
+use hyperliquid_rust_sdk::{
+    load_wallet_from_args, BaseUrl, ClientLimit, ClientOrder, ClientOrderRequest, ExchangeClient,
+    InfoClient, Tif,
+};
 
 #[tokio::main]
 async fn main() {
     env_logger::init();
-    // Key was randomly generated for testing and shouldn't be used with any real funds
-    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
-        .parse()
-        .unwrap();
+    // Key was randomly generated for testing and shouldn't be used with any real funds.
+    // Pass `--wallet env:VAR`, `--wallet file:PATH` or `--wallet keystore:PATH` to use your own.
+    let wallet =
+        load_wallet_from_args("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e")
+            .unwrap();
 
     let exchange_client = ExchangeClient::new(None, wallet, Some(BaseUrl::Testnet), None, None)
         .await
@@ -20,30 +24,53 @@ async fn main() {
         This agent cannot transfer or withdraw funds, but can for example place orders.
     */
 
-    let (private_key, response) = exchange_client.approve_agent(None).await.unwrap();
+    let (private_key, response) = exchange_client
+        .approve_named_agent("sdk-example", None)
+        .await
+        .unwrap();
     info!("Agent creation response: {response:?}");
 
     let wallet: LocalWallet = private_key.parse().unwrap();
 
     info!("Agent address: {:?}", wallet.address());
 
-    let exchange_client = ExchangeClient::new(None, wallet, Some(BaseUrl::Testnet), None, None)
+    let info_client = InfoClient::new(None, Some(BaseUrl::Testnet)).await.unwrap();
+    let agents = info_client
+        .extra_agents(exchange_client.wallet.address())
+        .await
+        .unwrap();
+    info!("Approved agents: {agents:?}");
+
+    let mut agent_client = ExchangeClient::new(None, wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    // Replaces the agent approved above with a fresh one under the same name
+    let (_private_key, response) = agent_client
+        .rotate_agent(Some("sdk-example"), &exchange_client.wallet)
+        .await
+        .unwrap();
+    info!("Agent rotation response: {response:?}");
+    info!("New agent address: {:?}", agent_client.wallet.address());
+
+    // The previous agent is no longer listed under the name
+    let agents = info_client
+        .extra_agents(exchange_client.wallet.address())
         .await
         .unwrap();
+    info!("Approved agents after rotation: {agents:?}");
 
     let order = ClientOrderRequest {
         asset: "ETH".to_string(),
         is_buy: true,
         reduce_only: false,
-        limit_px: 1795.0,
-        sz: 0.01,
+        limit_px: "1795".parse().unwrap(),
+        sz: "0.01".parse().unwrap(),
         cloid: None,
-        order_type: ClientOrder::Limit(ClientLimit {
-            tif: "Gtc".to_string(),
-        }),
+        order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Gtc }),
     };
 
-    let response = exchange_client.order(order, None).await.unwrap();
+    let response = agent_client.order(order, None).await.unwrap();
 
     info!("Order placed: {response:?}");
 }
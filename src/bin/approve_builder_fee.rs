@@ -1,14 +1,14 @@
-use ethers::signers::LocalWallet;
-use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient};
+use hyperliquid_rust_sdk::{load_wallet_from_args, BaseUrl, ExchangeClient};
 use log::info;
 
 #[tokio::main]
 async fn main() {
     env_logger::init();
-    // Key was randomly generated for testing and shouldn't be used with any real funds
-    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
-        .parse()
-        .unwrap();
+    // Key was randomly generated for testing and shouldn't be used with any real funds.
+    // Pass `--wallet env:VAR`, `--wallet file:PATH` or `--wallet keystore:PATH` to use your own.
+    let wallet =
+        load_wallet_from_args("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e")
+            .unwrap();
 
     let exchange_client =
         ExchangeClient::new(None, wallet.clone(), Some(BaseUrl::Testnet), None, None)
@@ -16,10 +16,12 @@ async fn main() {
             .unwrap();
 
     let max_fee_rate = "0.1%";
-    let builder = "0x1ab189B7801140900C711E458212F9c76F8dAC79".to_lowercase();
+    let builder = "0x1ab189B7801140900C711E458212F9c76F8dAC79"
+        .parse()
+        .unwrap();
 
     let resp = exchange_client
-        .approve_builder_fee(builder.to_string(), max_fee_rate.to_string(), Some(&wallet))
+        .approve_builder_fee(builder, max_fee_rate.to_string(), Some(&wallet))
         .await;
     info!("resp: {resp:#?}");
 }
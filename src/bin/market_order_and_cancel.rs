@@ -1,19 +1,19 @@
-use ethers::signers::LocalWallet;
 use log::info;
 
 use hyperliquid_rust_sdk::{
-    BaseUrl, ExchangeClient, ExchangeDataStatus, ExchangeResponseStatus, MarketCloseParams,
-    MarketOrderParams,
+    load_wallet_from_args, BaseUrl, ExchangeClient, ExchangeDataStatus, ExchangeResponseStatus,
+    MarketCloseParams, MarketOrderParams,
 };
 use std::{thread::sleep, time::Duration};
 
 #[tokio::main]
 async fn main() {
     env_logger::init();
-    // Key was randomly generated for testing and shouldn't be used with any real funds
-    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
-        .parse()
-        .unwrap();
+    // Key was randomly generated for testing and shouldn't be used with any real funds.
+    // Pass `--wallet env:VAR`, `--wallet file:PATH` or `--wallet keystore:PATH` to use your own.
+    let wallet =
+        load_wallet_from_args("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e")
+            .unwrap();
 
     let exchange_client = ExchangeClient::new(None, wallet, Some(BaseUrl::Testnet), None, None)
         .await
@@ -23,7 +23,7 @@ async fn main() {
     let market_open_params = MarketOrderParams {
         asset: "ETH",
         is_buy: true,
-        sz: 0.01,
+        sz: "0.01".parse().unwrap(),
         px: None,
         slippage: Some(0.01), // 1% slippage
         cloid: None,
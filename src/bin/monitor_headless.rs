@@ -119,10 +119,10 @@ async fn monitor_address(
                         let tx = Transaction {
                             timestamp: fill.time as i64,
                             token: fill.coin,
-                            side: fill.side,
-                            size: fill.sz.parse::<f64>().unwrap_or(0.0),
+                            side: fill.side.to_string(),
+                            size: fill.sz.to_f64(),
                             leverage: 1.0,
-                            entry_price: fill.px.parse::<f64>().unwrap_or(0.0),
+                            entry_price: fill.px.to_f64(),
                         };
                         
                         let mut txs = transactions.lock().unwrap();
@@ -175,10 +175,10 @@ async fn monitor_address(
                             let tx = Transaction {
                                 timestamp: fill.time as i64,
                                 token: fill.coin,
-                                side: fill.side,
-                                size: fill.sz.parse::<f64>().unwrap_or(0.0),
+                                side: fill.side.to_string(),
+                                size: fill.sz.to_f64(),
                                 leverage: 1.0,
-                                entry_price: fill.px.parse::<f64>().unwrap_or(0.0),
+                                entry_price: fill.px.to_f64(),
                             };
                             
                             new_transactions.push(tx);
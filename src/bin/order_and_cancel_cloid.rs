@@ -1,8 +1,8 @@
-use ethers::signers::LocalWallet;
 use log::info;
 
 use hyperliquid_rust_sdk::{
-    BaseUrl, ClientCancelRequestCloid, ClientLimit, ClientOrder, ClientOrderRequest, ExchangeClient,
+    load_wallet_from_args, BaseUrl, ClientCancelRequestCloid, ClientLimit, ClientOrder,
+    ClientOrderRequest, ExchangeClient, Tif,
 };
 use std::{thread::sleep, time::Duration};
 use uuid::Uuid;
@@ -10,10 +10,11 @@ use uuid::Uuid;
 #[tokio::main]
 async fn main() {
     env_logger::init();
-    // Key was randomly generated for testing and shouldn't be used with any real funds
-    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
-        .parse()
-        .unwrap();
+    // Key was randomly generated for testing and shouldn't be used with any real funds.
+    // Pass `--wallet env:VAR`, `--wallet file:PATH` or `--wallet keystore:PATH` to use your own.
+    let wallet =
+        load_wallet_from_args("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e")
+            .unwrap();
 
     let exchange_client = ExchangeClient::new(None, wallet, Some(BaseUrl::Testnet), None, None)
         .await
@@ -25,12 +26,10 @@ async fn main() {
         asset: "ETH".to_string(),
         is_buy: true,
         reduce_only: false,
-        limit_px: 1800.0,
-        sz: 0.01,
+        limit_px: "1800".parse().unwrap(),
+        sz: "0.01".parse().unwrap(),
         cloid: Some(cloid),
-        order_type: ClientOrder::Limit(ClientLimit {
-            tif: "Gtc".to_string(),
-        }),
+        order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Gtc }),
     };
 
     let response = exchange_client.order(order, None).await.unwrap();
@@ -0,0 +1,519 @@
+use crate::{prelude::*, Error};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{cmp::Ordering, fmt, ops::Neg, str::FromStr};
+
+/// Direction used when a value has to lose precision.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Half away from zero.
+    #[default]
+    Nearest,
+    /// Towards negative infinity.
+    Down,
+    /// Towards positive infinity.
+    Up,
+}
+
+/// Exact base-10 number used for prices and sizes.
+///
+/// The value is `mantissa * 10^-scale`, always kept normalized (no trailing zeros in the
+/// fractional part), so the `Display` output is exactly the string that gets hashed and sent
+/// over the wire. The mantissa is never `i128::MIN`, so negation cannot overflow; arithmetic
+/// goes through the `checked_*` methods.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    pub const MAX_SCALE: u32 = 28;
+    pub const ZERO: Decimal = Decimal {
+        mantissa: 0,
+        scale: 0,
+    };
+    pub const ONE: Decimal = Decimal {
+        mantissa: 1,
+        scale: 0,
+    };
+
+    /// `Decimal::new(2378, 8)` is `0.00002378`. Fails if `scale` is above [`Self::MAX_SCALE`].
+    pub fn new(mantissa: i64, scale: u32) -> Result<Decimal> {
+        if scale > Self::MAX_SCALE {
+            return Err(Error::DecimalOverflow(format!(
+                "scale {scale} is above {}",
+                Self::MAX_SCALE
+            )));
+        }
+        Ok(Self::from_parts(mantissa as i128, scale))
+    }
+
+    fn checked_parts(mantissa: i128, scale: u32) -> Option<Decimal> {
+        (mantissa != i128::MIN).then(|| Self::from_parts(mantissa, scale))
+    }
+
+    fn from_parts(mut mantissa: i128, mut scale: u32) -> Decimal {
+        if mantissa == 0 {
+            return Self::ZERO;
+        }
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        Decimal { mantissa, scale }
+    }
+
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// Number of digits after the decimal point.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.mantissa < 0
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.mantissa > 0
+    }
+
+    pub fn abs(&self) -> Decimal {
+        // cannot overflow as the mantissa is never `i128::MIN`
+        Decimal {
+            mantissa: self.mantissa.abs(),
+            scale: self.scale,
+        }
+    }
+
+    /// Number of significant digits, e.g. 3 for both `123` and `0.00123`.
+    pub fn significant_figures(&self) -> u32 {
+        num_digits(self.mantissa.unsigned_abs())
+    }
+
+    /// Rounds half away from zero to at most `decimals` digits after the decimal point.
+    pub fn round_dp(&self, decimals: u32) -> Decimal {
+        self.round_dp_with(decimals, RoundingMode::Nearest)
+    }
+
+    pub fn round_dp_with(&self, decimals: u32, mode: RoundingMode) -> Decimal {
+        if self.scale <= decimals {
+            return *self;
+        }
+        let factor = pow10(self.scale - decimals);
+        Self::from_parts(div_round(self.mantissa, factor, mode), decimals)
+    }
+
+    /// Rounds half away from zero to at most `sig_figs` significant figures. Fails if rounding
+    /// up an integer overflows, e.g. 39 nines to one significant figure.
+    pub fn round_sf(&self, sig_figs: u32) -> Result<Decimal> {
+        self.round_sf_with(sig_figs, RoundingMode::Nearest)
+    }
+
+    pub fn round_sf_with(&self, sig_figs: u32, mode: RoundingMode) -> Result<Decimal> {
+        let digits = self.significant_figures();
+        if digits <= sig_figs {
+            return Ok(*self);
+        }
+        let overflow =
+            || Error::DecimalOverflow(format!("{self} to {sig_figs} significant figures"));
+        let dropped = digits - sig_figs;
+        let rounded = div_round(
+            self.mantissa,
+            pow10_checked(dropped).ok_or_else(overflow)?,
+            mode,
+        );
+        // the dropped digits come off the scale first, only integers are scaled back up
+        if dropped <= self.scale {
+            return Ok(Self::from_parts(rounded, self.scale - dropped));
+        }
+        pow10_checked(dropped - self.scale)
+            .and_then(|factor| rounded.checked_mul(factor))
+            .and_then(|mantissa| Self::checked_parts(mantissa, 0))
+            .ok_or_else(overflow)
+    }
+
+    /// Lossy conversion, only meant for display or heuristics such as slippage estimates.
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(0.0)
+    }
+
+    fn rescaled(&self, scale: u32) -> Option<i128> {
+        self.mantissa
+            .checked_mul(pow10_checked(scale - self.scale)?)
+    }
+
+    /// Integer part and fractional digits, both truncated towards zero.
+    fn split(&self) -> (i128, i128) {
+        let factor = pow10(self.scale);
+        (self.mantissa / factor, self.mantissa % factor)
+    }
+
+    fn aligned(&self, other: &Decimal) -> Option<(i128, i128, u32)> {
+        let scale = self.scale.max(other.scale);
+        Some((self.rescaled(scale)?, other.rescaled(scale)?, scale))
+    }
+
+    pub fn checked_add(&self, other: Decimal) -> Option<Decimal> {
+        let (a, b, scale) = self.aligned(&other)?;
+        Self::checked_parts(a.checked_add(b)?, scale)
+    }
+
+    pub fn checked_sub(&self, other: Decimal) -> Option<Decimal> {
+        let (a, b, scale) = self.aligned(&other)?;
+        Self::checked_parts(a.checked_sub(b)?, scale)
+    }
+
+    pub fn checked_mul(&self, other: Decimal) -> Option<Decimal> {
+        let product = Self::checked_parts(
+            self.mantissa.checked_mul(other.mantissa)?,
+            self.scale + other.scale,
+        )?;
+        Some(product.round_dp(Self::MAX_SCALE))
+    }
+}
+
+fn pow10_checked(exp: u32) -> Option<i128> {
+    10i128.checked_pow(exp)
+}
+
+fn pow10(exp: u32) -> i128 {
+    pow10_checked(exp).expect("decimal scale out of range")
+}
+
+fn div_round(value: i128, divisor: i128, mode: RoundingMode) -> i128 {
+    let quotient = value / divisor;
+    let remainder = value % divisor;
+    let round_away = match mode {
+        RoundingMode::Nearest => remainder.unsigned_abs() * 2 >= divisor.unsigned_abs(),
+        RoundingMode::Down => remainder < 0,
+        RoundingMode::Up => remainder > 0,
+    };
+    if round_away {
+        quotient + value.signum()
+    } else {
+        quotient
+    }
+}
+
+fn num_digits(mut value: u128) -> u32 {
+    let mut digits = 0;
+    while value > 0 {
+        value /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+impl FromStr for Decimal {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Decimal> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+        if (int_part.is_empty() && frac_part.is_empty())
+            || frac_part.len() > Self::MAX_SCALE as usize
+            || !int_part
+                .bytes()
+                .chain(frac_part.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return Err(Error::FloatStringParse);
+        }
+
+        let mut mantissa: i128 = 0;
+        for b in int_part.bytes().chain(frac_part.bytes()) {
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add((b - b'0') as i128))
+                .ok_or(Error::FloatStringParse)?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+        Ok(Self::from_parts(mantissa, frac_part.len() as u32))
+    }
+}
+
+impl TryFrom<f64> for Decimal {
+    type Error = Error;
+
+    /// Uses the shortest representation that round-trips to the same `f64`, so `0.1` becomes
+    /// exactly `0.1` rather than its binary approximation.
+    fn try_from(value: f64) -> Result<Decimal> {
+        if !value.is_finite() {
+            return Err(Error::FloatStringParse);
+        }
+        format!("{value}").parse()
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Decimal {
+        Self::from_parts(value as i128, 0)
+    }
+}
+
+impl From<u64> for Decimal {
+    fn from(value: u64) -> Decimal {
+        Self::from_parts(value as i128, 0)
+    }
+}
+
+impl From<u32> for Decimal {
+    fn from(value: u32) -> Decimal {
+        Self::from_parts(value as i128, 0)
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let sign = if self.is_negative() { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+        if digits.len() > scale {
+            let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+            write!(f, "{sign}{int_part}.{frac_part}")
+        } else {
+            write!(f, "{sign}0.{digits:0>scale$}")
+        }
+    }
+}
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if let Some((a, b, _)) = self.aligned(other) {
+            return a.cmp(&b);
+        }
+        // aligning overflowed, so compare the integer parts and then the fractions, which have
+        // the sign of their value and always fit when scaled to `MAX_SCALE`
+        let (a_int, a_frac) = self.split();
+        let (b_int, b_frac) = other.split();
+        a_int.cmp(&b_int).then_with(|| {
+            let scale = self.scale.max(other.scale);
+            (a_frac * pow10(scale - self.scale)).cmp(&(b_frac * pow10(scale - other.scale)))
+        })
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    /// Cannot overflow as the mantissa is never `i128::MIN`.
+    fn neg(self) -> Decimal {
+        Decimal {
+            mantissa: -self.mantissa,
+            scale: self.scale,
+        }
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+struct DecimalVisitor;
+
+impl<'de> de::Visitor<'de> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a decimal number or numeric string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Decimal, E> {
+        v.parse().map_err(de::Error::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<Decimal, E> {
+        Ok(Decimal::from(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<Decimal, E> {
+        Ok(Decimal::from(v))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> std::result::Result<Decimal, E> {
+        Decimal::try_from(v).map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Decimal, D::Error> {
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_test() {
+        assert_eq!(dec("0").to_string(), "0");
+        assert_eq!(dec("-0.000").to_string(), "0");
+        assert_eq!(dec("2000.0").to_string(), "2000");
+        assert_eq!(dec("0.00076000").to_string(), "0.00076");
+        assert_eq!(dec("-12.50").to_string(), "-12.5");
+        assert_eq!(dec(".5").to_string(), "0.5");
+        assert_eq!(dec("+3").to_string(), "3");
+        assert_eq!(
+            dec("123456789012345678.123456789").to_string(),
+            "123456789012345678.123456789"
+        );
+        assert!("".parse::<Decimal>().is_err());
+        assert!("-".parse::<Decimal>().is_err());
+        assert!("1e5".parse::<Decimal>().is_err());
+        assert!("1.2.3".parse::<Decimal>().is_err());
+        assert!("abc".parse::<Decimal>().is_err());
+    }
+
+    #[test]
+    fn from_f64_test() {
+        let cases = [
+            (0., "0"),
+            (-0., "0"),
+            (0.00076000, "0.00076"),
+            (0.00000001, "0.00000001"),
+            (0.12345678, "0.12345678"),
+            (87654321.12345678, "87654321.12345678"),
+            (987654321.00000000, "987654321"),
+            (87654321.1234, "87654321.1234"),
+            (0.1, "0.1"),
+            (0.000000001, "0.000000001"),
+            (1e21, "1000000000000000000000"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(Decimal::try_from(value).unwrap().to_string(), expected);
+        }
+        assert!(Decimal::try_from(f64::NAN).is_err());
+        assert!(Decimal::try_from(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn rounding_test() {
+        assert_eq!(dec("1.2345").round_dp(2), dec("1.23"));
+        assert_eq!(dec("1.235").round_dp(2), dec("1.24"));
+        assert_eq!(dec("-1.235").round_dp(2), dec("-1.24"));
+        assert_eq!(dec("1.2").round_dp(4), dec("1.2"));
+        assert_eq!(dec("0.000123456").round_sf(5).unwrap(), dec("0.00012346"));
+        assert_eq!(dec("123456").round_sf(5).unwrap(), dec("123460"));
+        assert_eq!(dec("99999.5").round_sf(5).unwrap(), dec("100000"));
+        assert_eq!(dec("2000.123").significant_figures(), 7);
+
+        assert_eq!(
+            dec("1.239").round_dp_with(2, RoundingMode::Down),
+            dec("1.23")
+        );
+        assert_eq!(dec("1.231").round_dp_with(2, RoundingMode::Up), dec("1.24"));
+        assert_eq!(
+            dec("-1.231").round_dp_with(2, RoundingMode::Down),
+            dec("-1.24")
+        );
+        assert_eq!(
+            dec("-1.239").round_dp_with(2, RoundingMode::Up),
+            dec("-1.23")
+        );
+        assert_eq!(dec("1.23").round_dp_with(2, RoundingMode::Up), dec("1.23"));
+        assert_eq!(
+            dec("123451").round_sf_with(5, RoundingMode::Up).unwrap(),
+            dec("123460")
+        );
+        assert_eq!(
+            dec("123459").round_sf_with(5, RoundingMode::Down).unwrap(),
+            dec("123450")
+        );
+    }
+
+    #[test]
+    fn arithmetic_and_ordering_test() {
+        assert_eq!(dec("0.1").checked_add(dec("0.2")), Some(dec("0.3")));
+        assert_eq!(dec("1").checked_sub(dec("1.5")), Some(dec("-0.5")));
+        assert_eq!(dec("1.5").checked_mul(dec("0.02")), Some(dec("0.03")));
+        assert_eq!(-dec("2.5"), dec("-2.5"));
+        assert!(dec("0.3") > dec("0.29999"));
+        assert!(dec("-1") < Decimal::ZERO);
+        assert_eq!(dec("10.0").cmp(&dec("10")), Ordering::Equal);
+
+        // 30 integer digits cannot be aligned to 10 decimals without overflowing
+        let big = dec("123456789012345678901234567890");
+        let small = dec("0.0000000001");
+        assert!(big > big.checked_sub(Decimal::ONE).unwrap());
+        assert!(-big < small && small < big);
+        assert_eq!(big.checked_mul(big), None);
+
+        // i128::MIN is never produced, so negating the extremes is exact
+        let max = dec(&i128::MAX.to_string());
+        let min = (-max).checked_sub(Decimal::ONE);
+        assert_eq!(min, None);
+        assert_eq!(-(-max), max);
+        assert_eq!((-max).abs(), max);
+    }
+
+    #[test]
+    fn construction_test() {
+        assert_eq!(Decimal::new(2378, 8).unwrap(), dec("0.00002378"));
+        assert_eq!(
+            Decimal::new(1, Decimal::MAX_SCALE).unwrap().to_string(),
+            format!("0.{}1", "0".repeat(27))
+        );
+        assert!(matches!(
+            Decimal::new(1, 30),
+            Err(Error::DecimalOverflow(_))
+        ));
+
+        // rounding the largest integers up no longer fits
+        let nines = dec(&"9".repeat(38));
+        assert_eq!(
+            nines.round_sf(1).unwrap(),
+            dec(&format!("1{}", "0".repeat(38)))
+        );
+        let max = dec(&i128::MAX.to_string());
+        assert!(matches!(max.round_sf(1), Err(Error::DecimalOverflow(_))));
+        assert_eq!(
+            dec("0.99999999999999999999999999").round_sf(3).unwrap(),
+            Decimal::ONE
+        );
+    }
+
+    #[test]
+    fn serde_test() {
+        let value: Decimal = serde_json::from_str("\"1800.50\"").unwrap();
+        assert_eq!(value, dec("1800.5"));
+        let value: Decimal = serde_json::from_str("42").unwrap();
+        assert_eq!(value, dec("42"));
+        assert_eq!(serde_json::to_string(&dec("0.0100")).unwrap(), "\"0.01\"");
+    }
+}
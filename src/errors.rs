@@ -1,3 +1,5 @@
+use crate::exchange::OrderValidationError;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug, Clone)]
@@ -17,6 +19,10 @@ pub enum Error {
     },
     #[error("Generic request error: {0:?}")]
     GenericRequest(String),
+    #[error("Could not connect: {0}")]
+    Connect(String),
+    #[error("Request timed out: {0}")]
+    Timeout(String),
     #[error("Chain type not allowed for this function")]
     ChainNotAllowed,
     #[error("Asset not found")]
@@ -55,10 +61,26 @@ pub enum Error {
     RmpParse(String),
     #[error("Invalid input number")]
     FloatStringParse,
+    #[error("Decimal overflow: {0}")]
+    DecimalOverflow(String),
     #[error("No cloid found in order request when expected")]
     NoCloid,
     #[error("ECDSA signature failed: {0:?}")]
     SignatureFailure(String),
     #[error("Vault address not found")]
     VaultAddressNotFound,
+    #[error("Price {0} is not a valid tick for this asset")]
+    InvalidPrice(String),
+    #[error("Size {0} has more decimals than allowed for this asset")]
+    InvalidSize(String),
+    #[error("Nonce error: {0:?}")]
+    Nonce(String),
+    #[error("Rate limit reached, a request of weight {weight} can be sent in {retry_after:?}")]
+    RateLimited { weight: u32, retry_after: Duration },
+    #[error("Dead man's switch error: {0}")]
+    DeadMansSwitch(String),
+    #[error("Unsupported action: {0}")]
+    UnsupportedAction(String),
+    #[error("Order validation failed: {0}")]
+    OrderValidation(OrderValidationError),
 }
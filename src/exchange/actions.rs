@@ -1,18 +1,23 @@
-use crate::exchange::{cancel::CancelRequest, modify::ModifyRequest, order::OrderRequest};
+use crate::exchange::{
+    cancel::CancelRequest,
+    modify::ModifyRequest,
+    order::{Grouping, OrderRequest},
+    twap::TwapRequest,
+};
 pub(crate) use ethers::{
-    abi::{encode, ParamType, Tokenizable},
+    abi::{encode, ParamType, Token, Tokenizable},
     types::{
         transaction::{
             eip712,
             eip712::{encode_eip712_type, EIP712Domain, Eip712, Eip712Error},
         },
-        H160, U256,
+        Signature, H160, H256, U256,
     },
     utils::keccak256,
 };
 use serde::{Deserialize, Serialize};
 
-use super::{cancel::CancelRequestCloid, BuilderInfo};
+use super::{cancel::CancelRequestCloid, Actions, BuilderInfo};
 
 pub(crate) const HYPERLIQUID_EIP_PREFIX: &str = "HyperliquidTransaction:";
 
@@ -30,6 +35,51 @@ fn eip_712_domain(chain_id: U256) -> EIP712Domain {
     }
 }
 
+/// An action the user signs as the EIP-712 typed data `HyperliquidTransaction:<TYPE>`, rather
+/// than as an L1 action. The first field of the type is always `hyperliquidChain`.
+pub trait UserSignedAction: Eip712 + Clone + Send + Sync {
+    const TYPE: &'static str;
+
+    fn signature_chain_id(&self) -> U256;
+
+    fn hyperliquid_chain(&self) -> &str;
+
+    /// Names and types of the fields after `hyperliquidChain`.
+    fn field_types() -> Vec<(&'static str, ParamType)>;
+
+    /// Values of the fields after `hyperliquidChain`.
+    fn field_tokens(&self) -> Vec<Token>;
+}
+
+/// Type hash of `T`, with `extra` fields inserted after `hyperliquidChain`.
+fn user_signed_type_hash<T: UserSignedAction>(extra: &[(&str, ParamType)]) -> [u8; 32] {
+    let fields: Vec<_> = [("hyperliquidChain", ParamType::String)]
+        .iter()
+        .chain(extra)
+        .cloned()
+        .chain(T::field_types())
+        .map(|(name, param_type)| (name.to_string(), param_type))
+        .collect();
+    eip712::make_type_hash(format!("{HYPERLIQUID_EIP_PREFIX}{}", T::TYPE), &fields)
+}
+
+fn user_signed_struct_hash<T: UserSignedAction>(
+    action: &T,
+    extra: &[(&str, ParamType)],
+    extra_tokens: Vec<Token>,
+) -> [u8; 32] {
+    let items: Vec<_> = [
+        Token::Uint(user_signed_type_hash::<T>(extra).into()),
+        action.hyperliquid_chain().to_string().into_token(),
+    ]
+    .into_iter()
+    .chain(extra_tokens)
+    .chain(action.field_tokens())
+    .map(encode_eip712_type)
+    .collect();
+    keccak256(encode(&items))
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UsdSend {
@@ -40,6 +90,34 @@ pub struct UsdSend {
     pub time: u64,
 }
 
+impl UserSignedAction for UsdSend {
+    const TYPE: &'static str = "UsdSend";
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+
+    fn hyperliquid_chain(&self) -> &str {
+        &self.hyperliquid_chain
+    }
+
+    fn field_types() -> Vec<(&'static str, ParamType)> {
+        vec![
+            ("destination", ParamType::String),
+            ("amount", ParamType::String),
+            ("time", ParamType::Uint(64)),
+        ]
+    }
+
+    fn field_tokens(&self) -> Vec<Token> {
+        vec![
+            self.destination.clone().into_token(),
+            self.amount.clone().into_token(),
+            self.time.into_token(),
+        ]
+    }
+}
+
 impl Eip712 for UsdSend {
     type Error = Eip712Error;
 
@@ -48,33 +126,11 @@ impl Eip712 for UsdSend {
     }
 
     fn type_hash() -> Result<[u8; 32], Self::Error> {
-        Ok(eip712::make_type_hash(
-            format!("{HYPERLIQUID_EIP_PREFIX}UsdSend"),
-            &[
-                ("hyperliquidChain".to_string(), ParamType::String),
-                ("destination".to_string(), ParamType::String),
-                ("amount".to_string(), ParamType::String),
-                ("time".to_string(), ParamType::Uint(64)),
-            ],
-        ))
+        Ok(user_signed_type_hash::<Self>(&[]))
     }
 
     fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
-        let Self {
-            signature_chain_id: _,
-            hyperliquid_chain,
-            destination,
-            amount,
-            time,
-        } = self;
-        let items = vec![
-            ethers::abi::Token::Uint(Self::type_hash()?.into()),
-            encode_eip712_type(hyperliquid_chain.clone().into_token()),
-            encode_eip712_type(destination.clone().into_token()),
-            encode_eip712_type(amount.clone().into_token()),
-            encode_eip712_type(time.into_token()),
-        ];
-        Ok(keccak256(encode(&items)))
+        Ok(user_signed_struct_hash(self, &[], vec![]))
     }
 }
 
@@ -98,7 +154,7 @@ pub struct UpdateIsolatedMargin {
 #[serde(rename_all = "camelCase")]
 pub struct BulkOrder {
     pub orders: Vec<OrderRequest>,
-    pub grouping: String,
+    pub grouping: Grouping,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub builder: Option<BuilderInfo>,
 }
@@ -109,6 +165,19 @@ pub struct BulkCancel {
     pub cancels: Vec<CancelRequest>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TwapOrder {
+    pub twap: TwapRequest,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TwapCancel {
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: u32,
+    #[serde(rename = "t", alias = "twapId")]
+    pub twap_id: u64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct BulkModify {
@@ -127,10 +196,39 @@ pub struct ApproveAgent {
     pub signature_chain_id: U256,
     pub hyperliquid_chain: String,
     pub agent_address: H160,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub agent_name: Option<String>,
     pub nonce: u64,
 }
 
+impl UserSignedAction for ApproveAgent {
+    const TYPE: &'static str = "ApproveAgent";
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+
+    fn hyperliquid_chain(&self) -> &str {
+        &self.hyperliquid_chain
+    }
+
+    fn field_types() -> Vec<(&'static str, ParamType)> {
+        vec![
+            ("agentAddress", ParamType::Address),
+            ("agentName", ParamType::String),
+            ("nonce", ParamType::Uint(64)),
+        ]
+    }
+
+    fn field_tokens(&self) -> Vec<Token> {
+        vec![
+            self.agent_address.into_token(),
+            self.agent_name.clone().unwrap_or_default().into_token(),
+            self.nonce.into_token(),
+        ]
+    }
+}
+
 impl Eip712 for ApproveAgent {
     type Error = Eip712Error;
 
@@ -139,33 +237,11 @@ impl Eip712 for ApproveAgent {
     }
 
     fn type_hash() -> Result<[u8; 32], Self::Error> {
-        Ok(eip712::make_type_hash(
-            format!("{HYPERLIQUID_EIP_PREFIX}ApproveAgent"),
-            &[
-                ("hyperliquidChain".to_string(), ParamType::String),
-                ("agentAddress".to_string(), ParamType::Address),
-                ("agentName".to_string(), ParamType::String),
-                ("nonce".to_string(), ParamType::Uint(64)),
-            ],
-        ))
+        Ok(user_signed_type_hash::<Self>(&[]))
     }
 
     fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
-        let Self {
-            signature_chain_id: _,
-            hyperliquid_chain,
-            agent_address,
-            agent_name,
-            nonce,
-        } = self;
-        let items = vec![
-            ethers::abi::Token::Uint(Self::type_hash()?.into()),
-            encode_eip712_type(hyperliquid_chain.clone().into_token()),
-            encode_eip712_type(agent_address.into_token()),
-            encode_eip712_type(agent_name.clone().unwrap_or_default().into_token()),
-            encode_eip712_type(nonce.into_token()),
-        ];
-        Ok(keccak256(encode(&items)))
+        Ok(user_signed_struct_hash(self, &[], vec![]))
     }
 }
 
@@ -179,6 +255,34 @@ pub struct Withdraw3 {
     pub destination: String,
 }
 
+impl UserSignedAction for Withdraw3 {
+    const TYPE: &'static str = "Withdraw";
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+
+    fn hyperliquid_chain(&self) -> &str {
+        &self.hyperliquid_chain
+    }
+
+    fn field_types() -> Vec<(&'static str, ParamType)> {
+        vec![
+            ("destination", ParamType::String),
+            ("amount", ParamType::String),
+            ("time", ParamType::Uint(64)),
+        ]
+    }
+
+    fn field_tokens(&self) -> Vec<Token> {
+        vec![
+            self.destination.clone().into_token(),
+            self.amount.clone().into_token(),
+            self.time.into_token(),
+        ]
+    }
+}
+
 impl Eip712 for Withdraw3 {
     type Error = Eip712Error;
 
@@ -186,14 +290,263 @@ impl Eip712 for Withdraw3 {
         Ok(eip_712_domain(self.signature_chain_id))
     }
 
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_type_hash::<Self>(&[]))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_struct_hash(self, &[], vec![]))
+    }
+}
+
+/// Moves HYPE from the spot balance into staking.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CDeposit {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub wei: u64,
+    pub nonce: u64,
+}
+
+impl UserSignedAction for CDeposit {
+    const TYPE: &'static str = "CDeposit";
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+
+    fn hyperliquid_chain(&self) -> &str {
+        &self.hyperliquid_chain
+    }
+
+    fn field_types() -> Vec<(&'static str, ParamType)> {
+        vec![("wei", ParamType::Uint(64)), ("nonce", ParamType::Uint(64))]
+    }
+
+    fn field_tokens(&self) -> Vec<Token> {
+        vec![self.wei.into_token(), self.nonce.into_token()]
+    }
+}
+
+impl Eip712 for CDeposit {
+    type Error = Eip712Error;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(eip_712_domain(self.signature_chain_id))
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_type_hash::<Self>(&[]))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_struct_hash(self, &[], vec![]))
+    }
+}
+
+/// Moves undelegated HYPE from staking back to the spot balance.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CWithdraw {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub wei: u64,
+    pub nonce: u64,
+}
+
+impl UserSignedAction for CWithdraw {
+    const TYPE: &'static str = "CWithdraw";
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+
+    fn hyperliquid_chain(&self) -> &str {
+        &self.hyperliquid_chain
+    }
+
+    fn field_types() -> Vec<(&'static str, ParamType)> {
+        vec![("wei", ParamType::Uint(64)), ("nonce", ParamType::Uint(64))]
+    }
+
+    fn field_tokens(&self) -> Vec<Token> {
+        vec![self.wei.into_token(), self.nonce.into_token()]
+    }
+}
+
+impl Eip712 for CWithdraw {
+    type Error = Eip712Error;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(eip_712_domain(self.signature_chain_id))
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_type_hash::<Self>(&[]))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_struct_hash(self, &[], vec![]))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDelegate {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub validator: H160,
+    pub wei: u64,
+    pub is_undelegate: bool,
+    pub nonce: u64,
+}
+
+impl UserSignedAction for TokenDelegate {
+    const TYPE: &'static str = "TokenDelegate";
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+
+    fn hyperliquid_chain(&self) -> &str {
+        &self.hyperliquid_chain
+    }
+
+    fn field_types() -> Vec<(&'static str, ParamType)> {
+        vec![
+            ("validator", ParamType::Address),
+            ("wei", ParamType::Uint(64)),
+            ("isUndelegate", ParamType::Bool),
+            ("nonce", ParamType::Uint(64)),
+        ]
+    }
+
+    fn field_tokens(&self) -> Vec<Token> {
+        vec![
+            self.validator.into_token(),
+            self.wei.into_token(),
+            self.is_undelegate.into_token(),
+            self.nonce.into_token(),
+        ]
+    }
+}
+
+impl Eip712 for TokenDelegate {
+    type Error = Eip712Error;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(eip_712_domain(self.signature_chain_id))
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_type_hash::<Self>(&[]))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_struct_hash(self, &[], vec![]))
+    }
+}
+
+/// Turns the signing user into a multi-sig user controlled by `signers`, the JSON of a
+/// [`MultiSigSigners`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertToMultiSigUser {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub signers: String,
+    pub nonce: u64,
+}
+
+impl UserSignedAction for ConvertToMultiSigUser {
+    const TYPE: &'static str = "ConvertToMultiSigUser";
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+
+    fn hyperliquid_chain(&self) -> &str {
+        &self.hyperliquid_chain
+    }
+
+    fn field_types() -> Vec<(&'static str, ParamType)> {
+        vec![
+            ("signers", ParamType::String),
+            ("nonce", ParamType::Uint(64)),
+        ]
+    }
+
+    fn field_tokens(&self) -> Vec<Token> {
+        vec![self.signers.clone().into_token(), self.nonce.into_token()]
+    }
+}
+
+impl Eip712 for ConvertToMultiSigUser {
+    type Error = Eip712Error;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(eip_712_domain(self.signature_chain_id))
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_type_hash::<Self>(&[]))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_struct_hash(self, &[], vec![]))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigSigners {
+    /// Sorted, as expected by the exchange
+    pub authorized_users: Vec<H160>,
+    pub threshold: u32,
+}
+
+/// An action of a multi-sig user, carrying the signatures of its authorized users. It is sent
+/// by one of them, the outer signer.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSig {
+    pub signature_chain_id: U256,
+    pub signatures: Vec<Signature>,
+    pub payload: MultiSigPayload,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigPayload {
+    pub multi_sig_user: H160,
+    pub outer_signer: H160,
+    pub action: Box<Actions>,
+}
+
+/// What the outer signer of a [`MultiSig`] signs.
+#[derive(Debug, Clone)]
+pub struct SendMultiSig {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub multi_sig_action_hash: H256,
+    pub nonce: u64,
+}
+
+impl Eip712 for SendMultiSig {
+    type Error = Eip712Error;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(eip_712_domain(self.signature_chain_id))
+    }
+
     fn type_hash() -> Result<[u8; 32], Self::Error> {
         Ok(eip712::make_type_hash(
-            format!("{HYPERLIQUID_EIP_PREFIX}Withdraw"),
+            format!("{HYPERLIQUID_EIP_PREFIX}SendMultiSig"),
             &[
                 ("hyperliquidChain".to_string(), ParamType::String),
-                ("destination".to_string(), ParamType::String),
-                ("amount".to_string(), ParamType::String),
-                ("time".to_string(), ParamType::Uint(64)),
+                ("multiSigActionHash".to_string(), ParamType::FixedBytes(32)),
+                ("nonce".to_string(), ParamType::Uint(64)),
             ],
         ))
     }
@@ -202,21 +555,60 @@ impl Eip712 for Withdraw3 {
         let Self {
             signature_chain_id: _,
             hyperliquid_chain,
-            amount,
-            time,
-            destination,
+            multi_sig_action_hash,
+            nonce,
         } = self;
         let items = vec![
             ethers::abi::Token::Uint(Self::type_hash()?.into()),
             encode_eip712_type(hyperliquid_chain.clone().into_token()),
-            encode_eip712_type(destination.clone().into_token()),
-            encode_eip712_type(amount.clone().into_token()),
-            encode_eip712_type(time.into_token()),
+            encode_eip712_type(multi_sig_action_hash.into_token()),
+            encode_eip712_type(nonce.into_token()),
         ];
         Ok(keccak256(encode(&items)))
     }
 }
 
+/// What an authorized user of a multi-sig user signs for a user-signed action: the typed data
+/// of the action with `payloadMultiSigUser` and `outerSigner` added after `hyperliquidChain`.
+#[derive(Debug, Clone)]
+pub struct MultiSigUserSigned<T> {
+    pub action: T,
+    pub multi_sig_user: H160,
+    pub outer_signer: H160,
+}
+
+impl<T: UserSignedAction> MultiSigUserSigned<T> {
+    fn extra_fields() -> [(&'static str, ParamType); 2] {
+        [
+            ("payloadMultiSigUser", ParamType::Address),
+            ("outerSigner", ParamType::Address),
+        ]
+    }
+}
+
+impl<T: UserSignedAction> Eip712 for MultiSigUserSigned<T> {
+    type Error = Eip712Error;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(eip_712_domain(self.action.signature_chain_id()))
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_type_hash::<T>(&Self::extra_fields()))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_struct_hash(
+            &self.action,
+            &Self::extra_fields(),
+            vec![
+                self.multi_sig_user.into_token(),
+                self.outer_signer.into_token(),
+            ],
+        ))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SpotSend {
@@ -228,6 +620,36 @@ pub struct SpotSend {
     pub time: u64,
 }
 
+impl UserSignedAction for SpotSend {
+    const TYPE: &'static str = "SpotSend";
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+
+    fn hyperliquid_chain(&self) -> &str {
+        &self.hyperliquid_chain
+    }
+
+    fn field_types() -> Vec<(&'static str, ParamType)> {
+        vec![
+            ("destination", ParamType::String),
+            ("token", ParamType::String),
+            ("amount", ParamType::String),
+            ("time", ParamType::Uint(64)),
+        ]
+    }
+
+    fn field_tokens(&self) -> Vec<Token> {
+        vec![
+            self.destination.clone().into_token(),
+            self.token.clone().into_token(),
+            self.amount.clone().into_token(),
+            self.time.into_token(),
+        ]
+    }
+}
+
 impl Eip712 for SpotSend {
     type Error = Eip712Error;
 
@@ -236,36 +658,11 @@ impl Eip712 for SpotSend {
     }
 
     fn type_hash() -> Result<[u8; 32], Self::Error> {
-        Ok(eip712::make_type_hash(
-            format!("{HYPERLIQUID_EIP_PREFIX}SpotSend"),
-            &[
-                ("hyperliquidChain".to_string(), ParamType::String),
-                ("destination".to_string(), ParamType::String),
-                ("token".to_string(), ParamType::String),
-                ("amount".to_string(), ParamType::String),
-                ("time".to_string(), ParamType::Uint(64)),
-            ],
-        ))
+        Ok(user_signed_type_hash::<Self>(&[]))
     }
 
     fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
-        let Self {
-            signature_chain_id: _,
-            hyperliquid_chain,
-            destination,
-            token,
-            amount,
-            time,
-        } = self;
-        let items = vec![
-            ethers::abi::Token::Uint(Self::type_hash()?.into()),
-            encode_eip712_type(hyperliquid_chain.clone().into_token()),
-            encode_eip712_type(destination.clone().into_token()),
-            encode_eip712_type(token.clone().into_token()),
-            encode_eip712_type(amount.clone().into_token()),
-            encode_eip712_type(time.into_token()),
-        ];
-        Ok(keccak256(encode(&items)))
+        Ok(user_signed_struct_hash(self, &[], vec![]))
     }
 }
 
@@ -290,18 +687,183 @@ pub struct VaultTransfer {
     pub usd: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateSubAccount {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountUsdTransfer {
+    pub sub_account_user: H160,
+    pub is_deposit: bool,
+    /// Amount in USDC units of 1e-6
+    pub usd: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountSpotTransfer {
+    pub sub_account_user: H160,
+    pub is_deposit: bool,
+    pub token: String,
+    pub amount: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVault {
+    pub name: String,
+    pub description: String,
+    /// Initial deposit of the leader in USDC units of 1e-6
+    pub initial_usd: u64,
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultModify {
+    pub vault_address: H160,
+    /// `None` leaves the setting unchanged
+    pub allow_deposits: Option<bool>,
+    pub always_close_on_withdraw: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDistribute {
+    pub vault_address: H160,
+    /// Amount in USDC units of 1e-6
+    pub usd: u64,
+}
+
+/// The HIP-1 token deployment steps, all sent as a `spotDeploy` action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum SpotDeploy {
+    RegisterToken2(RegisterToken),
+    UserGenesis(UserGenesis),
+    Genesis(Genesis),
+    RegisterSpot(RegisterSpot),
+    RegisterHyperliquidity(RegisterHyperliquidity),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSpec {
+    pub name: String,
+    pub sz_decimals: u32,
+    pub wei_decimals: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterToken {
+    pub spec: TokenSpec,
+    pub max_gas: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserGenesis {
+    pub token: u32,
+    /// Initial balances in wei of the new token
+    pub user_and_wei: Vec<(H160, String)>,
+    /// Balances given to the holders of existing tokens, proportional to their holdings
+    pub existing_token_and_wei: Vec<(u32, String)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Genesis {
+    pub token: u32,
+    pub max_supply: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_hyperliquidity: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RegisterSpot {
+    /// Base and quote token indices
+    pub tokens: [u32; 2],
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterHyperliquidity {
+    pub spot: u32,
+    pub start_px: String,
+    pub order_sz: String,
+    pub n_orders: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_seeded_levels: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SetReferrer {
     pub code: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScheduleCancel {
+    /// Time in milliseconds at which all open orders are cancelled, `None` removes the schedule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<u64>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ApproveBuilderFee {
     pub max_fee_rate: String,
-    pub builder: String,
+    pub builder: H160,
     pub nonce: u64,
     pub signature_chain_id: U256,
     pub hyperliquid_chain: String,
 }
+
+impl UserSignedAction for ApproveBuilderFee {
+    const TYPE: &'static str = "ApproveBuilderFee";
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+
+    fn hyperliquid_chain(&self) -> &str {
+        &self.hyperliquid_chain
+    }
+
+    fn field_types() -> Vec<(&'static str, ParamType)> {
+        vec![
+            ("maxFeeRate", ParamType::String),
+            ("builder", ParamType::Address),
+            ("nonce", ParamType::Uint(64)),
+        ]
+    }
+
+    fn field_tokens(&self) -> Vec<Token> {
+        vec![
+            self.max_fee_rate.clone().into_token(),
+            self.builder.into_token(),
+            self.nonce.into_token(),
+        ]
+    }
+}
+
+impl Eip712 for ApproveBuilderFee {
+    type Error = Eip712Error;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(eip_712_domain(self.signature_chain_id))
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_type_hash::<Self>(&[]))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(user_signed_struct_hash(self, &[], vec![]))
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    helpers::now_timestamp_ms, prelude::*, Error, ExchangeClient, ExchangeResponseStatus,
+    HyperliquidSigner,
+};
+use ethers::signers::LocalWallet;
+use log::error;
+use std::{sync::Arc, time::Duration};
+use tokio::{spawn, task::JoinHandle, time};
+
+/// Background task that keeps re-arming `scheduleCancel` while the process is healthy. If the
+/// process dies or hangs the schedule is no longer pushed back and the exchange cancels all open
+/// orders once `timeout` has passed since the last re-arm.
+#[derive(Debug)]
+pub struct DeadMansSwitch<S: HyperliquidSigner + 'static = LocalWallet> {
+    exchange_client: Arc<ExchangeClient<S>>,
+    handle: JoinHandle<()>,
+}
+
+impl<S: HyperliquidSigner + 'static> DeadMansSwitch<S> {
+    /// The exchange rejects schedules less than 5 seconds in the future.
+    pub const MIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Arms the switch every `rearm_interval`, each time scheduling the cancel `timeout` ahead.
+    /// Fails if `rearm_interval` is zero or not shorter than `timeout`, as the switch would then
+    /// fire between re-arms of a healthy process; it should be comfortably shorter.
+    pub fn spawn(
+        exchange_client: Arc<ExchangeClient<S>>,
+        timeout: Duration,
+        rearm_interval: Duration,
+    ) -> Result<DeadMansSwitch<S>> {
+        let timeout = timeout.max(Self::MIN_TIMEOUT);
+        if rearm_interval.is_zero() {
+            return Err(Error::DeadMansSwitch(
+                "re-arm interval must not be zero".to_string(),
+            ));
+        }
+        if rearm_interval >= timeout {
+            return Err(Error::DeadMansSwitch(format!(
+                "re-arm interval {rearm_interval:?} is not shorter than the timeout {timeout:?}"
+            )));
+        }
+
+        let client = Arc::clone(&exchange_client);
+        let handle = spawn(async move {
+            let mut interval = time::interval(rearm_interval);
+            loop {
+                interval.tick().await;
+                let cancel_time = now_timestamp_ms() + timeout.as_millis() as u64;
+                match client.schedule_cancel(Some(cancel_time), None).await {
+                    Ok(ExchangeResponseStatus::Err(err)) => {
+                        error!("Error re-arming dead man's switch: {err}")
+                    }
+                    Err(err) => error!("Error re-arming dead man's switch: {err}"),
+                    Ok(_) => {}
+                }
+            }
+        });
+
+        Ok(DeadMansSwitch {
+            exchange_client,
+            handle,
+        })
+    }
+
+    /// Stops re-arming and removes the schedule, so resting orders are left alone.
+    pub async fn disarm(self) -> Result<ExchangeResponseStatus> {
+        self.handle.abort();
+        self.exchange_client.schedule_cancel(None, None).await
+    }
+}
+
+impl<S: HyperliquidSigner + 'static> Drop for DeadMansSwitch<S> {
+    fn drop(&mut self) {
+        // the last schedule stays armed and fires unless `disarm` was called
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::exchange_client::tests::offline_client;
+
+    #[tokio::test]
+    async fn test_rejects_intervals_that_let_the_switch_fire() -> Result<()> {
+        let client = Arc::new(offline_client(r#"{"universe": []}"#, None)?);
+        let spawn = |timeout, rearm_interval| {
+            DeadMansSwitch::spawn(Arc::clone(&client), timeout, rearm_interval)
+        };
+        assert!(matches!(
+            spawn(Duration::from_secs(30), Duration::ZERO),
+            Err(Error::DeadMansSwitch(_))
+        ));
+        assert!(matches!(
+            spawn(Duration::from_secs(30), Duration::from_secs(30)),
+            Err(Error::DeadMansSwitch(_))
+        ));
+        // the timeout is raised to the minimum the exchange accepts
+        assert!(matches!(
+            spawn(Duration::from_secs(1), Duration::from_secs(5)),
+            Err(Error::DeadMansSwitch(_))
+        ));
+        Ok(())
+    }
+}
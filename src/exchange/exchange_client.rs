@@ -1,53 +1,152 @@
-use crate::signature::sign_typed_data;
 use crate::{
     exchange::{
         actions::{
-            ApproveAgent, ApproveBuilderFee, BulkCancel, BulkModify, BulkOrder, SetReferrer,
-            UpdateIsolatedMargin, UpdateLeverage, UsdSend,
+            ApproveAgent, ApproveBuilderFee, BulkCancel, BulkModify, BulkOrder, CreateSubAccount,
+            CreateVault, Genesis, RegisterHyperliquidity, RegisterSpot, RegisterToken,
+            ScheduleCancel, SetReferrer, SpotDeploy, SubAccountSpotTransfer, SubAccountUsdTransfer,
+            TokenSpec, TwapCancel, TwapOrder, UpdateIsolatedMargin, UpdateLeverage, UsdSend,
+            UserGenesis, VaultDistribute, VaultModify,
         },
         cancel::{CancelRequest, CancelRequestCloid},
         modify::{ClientModifyRequest, ModifyRequest},
-        ClientCancelRequest, ClientOrderRequest,
+        order::OrderRequest,
+        twap::{ClientTwapRequest, TwapRequest},
+        ClientCancelRequest, ClientOrderRequest, InMemoryNonceProvider, NonceProvider,
+        OrderNormalizer, OrderValidationContext,
     },
-    helpers::{generate_random_key, next_nonce, uuid_to_hex_string},
+    exchange_action_weight,
+    helpers::{generate_random_key, uuid_to_hex_string},
     info::info_client::InfoClient,
-    meta::Meta,
+    meta::{coin_perp_dex, perp_dex_coin, Meta, SpotMeta},
     prelude::*,
-    req::HttpClient,
-    signature::sign_l1_action,
-    BaseUrl, BulkCancelCloid, Error, ExchangeResponseStatus,
+    recover_l1_action_signer, recover_typed_data_signer,
+    req::{HttpClient, HttpConfig},
+    BaseUrl, BulkCancelCloid, Decimal, Error, ExchangeResponseStatus, HyperliquidSigner,
+    RateLimiter, RoundingMode,
+};
+use crate::{
+    CDeposit, CWithdraw, ClassTransfer, ConvertToMultiSigUser, MultiSig, MultiSigPayload,
+    MultiSigSigners, SendMultiSig, SpotSend, SpotUser, TokenDelegate, VaultTransfer, Withdraw3,
 };
-use crate::{ClassTransfer, SpotSend, SpotUser, VaultTransfer, Withdraw3};
 use ethers::{
     abi::AbiEncode,
-    signers::{LocalWallet, Signer},
+    signers::LocalWallet,
     types::{Signature, H160, H256},
 };
 use log::debug;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use super::cancel::ClientCancelRequestCloid;
 use super::order::{MarketCloseParams, MarketOrderParams};
-use super::{BuilderInfo, ClientLimit, ClientOrder};
+use super::{BuilderInfo, ClientLimit, ClientOrder, Grouping, Tif};
 
-#[derive(Debug)]
-pub struct ExchangeClient {
+#[derive(Debug, Clone)]
+pub struct ExchangeClient<S = LocalWallet> {
     pub http_client: HttpClient,
-    pub wallet: LocalWallet,
+    pub wallet: S,
     pub meta: Meta,
     pub vault_address: Option<H160>,
     pub coin_to_asset: HashMap<String, u32>,
+    pub order_normalizer: OrderNormalizer,
+    /// When set, orders are rounded with `order_normalizer` before being signed.
+    pub normalize_orders: bool,
+    /// When set, orders are checked with `ClientOrderRequest::validate` before being signed.
+    pub validate_orders: bool,
+    pub nonce_provider: Arc<dyn NonceProvider>,
+    /// Time in milliseconds after which the exchange rejects the actions of this client. It is
+    /// sent with every action and signed by L1 actions.
+    pub expires_after: Option<u64>,
 }
 
-#[derive(Serialize, Deserialize)]
+/// A signed `/exchange` request. Payloads can be signed offline with
+/// [`ExchangeClient::sign_action`], stored as JSON and submitted later with [`Self::submit`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
-struct ExchangePayload {
-    action: serde_json::Value,
-    signature: Signature,
-    nonce: u64,
-    vault_address: Option<H160>,
+pub struct ExchangePayload {
+    pub action: serde_json::Value,
+    pub signature: Signature,
+    pub nonce: u64,
+    pub vault_address: Option<H160>,
+    /// Time in milliseconds after which the exchange rejects the action.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<u64>,
+}
+
+impl ExchangePayload {
+    /// Sends the payload to `/exchange`, e.g. `submit::<ExchangeResponseStatus>`.
+    pub async fn submit<T: for<'a> Deserialize<'a>>(
+        &self,
+        client: Option<Client>,
+        base_url: Option<BaseUrl>,
+    ) -> Result<T> {
+        let http_client = HttpClient {
+            client: client.unwrap_or_default(),
+            base_url: base_url.unwrap_or(BaseUrl::Mainnet),
+            config: HttpConfig::default(),
+            rate_limiter: RateLimiter::shared(),
+        };
+        self.post(&http_client).await
+    }
+
+    async fn post<T: for<'a> Deserialize<'a>>(&self, http_client: &HttpClient) -> Result<T> {
+        let res = serde_json::to_string(self).map_err(|e| Error::JsonParse(e.to_string()))?;
+        debug!("Sending request {res:?}");
+
+        let output = &http_client
+            .post("/exchange", res, exchange_action_weight(&self.action))
+            .await?;
+        serde_json::from_str(output).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+
+    /// Recovers the address that signed the payload. For agent-signed L1 actions this is the
+    /// agent, and for multi-sig actions the outer signer.
+    pub fn recover_signer(&self, is_mainnet: bool) -> Result<H160> {
+        let action: Actions = serde_json::from_value(self.action.clone())
+            .map_err(|e| Error::JsonParse(e.to_string()))?;
+        let signature = &self.signature;
+
+        match &action {
+            Actions::UsdSend(usd_send) => recover_typed_data_signer(usd_send, signature),
+            Actions::Withdraw3(withdraw) => recover_typed_data_signer(withdraw, signature),
+            Actions::SpotSend(spot_send) => recover_typed_data_signer(spot_send, signature),
+            Actions::ApproveAgent(approve_agent) => {
+                recover_typed_data_signer(approve_agent, signature)
+            }
+            Actions::CDeposit(c_deposit) => recover_typed_data_signer(c_deposit, signature),
+            Actions::CWithdraw(c_withdraw) => recover_typed_data_signer(c_withdraw, signature),
+            Actions::TokenDelegate(token_delegate) => {
+                recover_typed_data_signer(token_delegate, signature)
+            }
+            Actions::ConvertToMultiSigUser(convert) => {
+                recover_typed_data_signer(convert, signature)
+            }
+            Actions::ApproveBuilderFee(approve_builder_fee) => {
+                recover_typed_data_signer(approve_builder_fee, signature)
+            }
+            Actions::MultiSig(multi_sig) => {
+                let multi_sig_action_hash = action_hash_with_expiry(
+                    multi_sig,
+                    self.nonce,
+                    self.vault_address,
+                    self.expires_after,
+                )?;
+                let envelope = SendMultiSig {
+                    signature_chain_id: 421614.into(),
+                    hyperliquid_chain: if is_mainnet { "Mainnet" } else { "Testnet" }.to_string(),
+                    multi_sig_action_hash,
+                    nonce: self.nonce,
+                };
+                recover_typed_data_signer(&envelope, signature)
+            }
+            _ => {
+                let connection_id =
+                    action.hash_with_expiry(self.nonce, self.vault_address, self.expires_after)?;
+                recover_l1_action_signer(connection_id, signature, is_mainnet)
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -68,136 +167,461 @@ pub enum Actions {
     SpotSend(SpotSend),
     SetReferrer(SetReferrer),
     ApproveBuilderFee(ApproveBuilderFee),
+    TwapOrder(TwapOrder),
+    TwapCancel(TwapCancel),
+    ScheduleCancel(ScheduleCancel),
+    CreateSubAccount(CreateSubAccount),
+    SubAccountTransfer(SubAccountUsdTransfer),
+    SubAccountSpotTransfer(SubAccountSpotTransfer),
+    CreateVault(CreateVault),
+    VaultModify(VaultModify),
+    VaultDistribute(VaultDistribute),
+    SpotDeploy(SpotDeploy),
+    CDeposit(CDeposit),
+    CWithdraw(CWithdraw),
+    TokenDelegate(TokenDelegate),
+    ConvertToMultiSigUser(ConvertToMultiSigUser),
+    MultiSig(MultiSig),
 }
 
 impl Actions {
-    fn hash(&self, timestamp: u64, vault_address: Option<H160>) -> Result<H256> {
-        let mut bytes =
-            rmp_serde::to_vec_named(self).map_err(|e| Error::RmpParse(e.to_string()))?;
-        bytes.extend(timestamp.to_be_bytes());
-        if let Some(vault_address) = vault_address {
-            bytes.push(1);
-            bytes.extend(vault_address.to_fixed_bytes());
-        } else {
-            bytes.push(0);
+    /// Whether the action is signed by the user as EIP-712 typed data rather than as an L1
+    /// action, see [`UserSignedAction`](crate::UserSignedAction).
+    pub fn is_user_signed(&self) -> bool {
+        matches!(
+            self,
+            Actions::UsdSend(_)
+                | Actions::Withdraw3(_)
+                | Actions::SpotSend(_)
+                | Actions::ApproveAgent(_)
+                | Actions::CDeposit(_)
+                | Actions::CWithdraw(_)
+                | Actions::TokenDelegate(_)
+                | Actions::ConvertToMultiSigUser(_)
+                | Actions::ApproveBuilderFee(_)
+        )
+    }
+
+    /// The msgpack encoding of the action, as hashed into its connection id.
+    pub fn to_msgpack(&self) -> Result<Vec<u8>> {
+        rmp_serde::to_vec_named(self).map_err(|e| Error::RmpParse(e.to_string()))
+    }
+
+    /// The connection id that L1 actions sign, see [`action_hash`].
+    pub fn hash(&self, timestamp: u64, vault_address: Option<H160>) -> Result<H256> {
+        action_hash(self, timestamp, vault_address)
+    }
+
+    /// The connection id of an action that expires, see [`action_hash_with_expiry`].
+    pub fn hash_with_expiry(
+        &self,
+        timestamp: u64,
+        vault_address: Option<H160>,
+        expires_after: Option<u64>,
+    ) -> Result<H256> {
+        action_hash_with_expiry(self, timestamp, vault_address, expires_after)
+    }
+}
+
+/// Keccak hash of the msgpack encoding of `action`, followed by the big endian nonce and the
+/// vault address (a `0` byte, or a `1` byte and the address). L1 actions sign it as their
+/// connection id.
+pub fn action_hash<T: Serialize>(
+    action: &T,
+    timestamp: u64,
+    vault_address: Option<H160>,
+) -> Result<H256> {
+    action_hash_with_expiry(action, timestamp, vault_address, None)
+}
+
+/// Like [`action_hash`], with a `0` byte and the big endian `expires_after` appended when set.
+pub fn action_hash_with_expiry<T: Serialize>(
+    action: &T,
+    timestamp: u64,
+    vault_address: Option<H160>,
+    expires_after: Option<u64>,
+) -> Result<H256> {
+    let mut bytes = rmp_serde::to_vec_named(action).map_err(|e| Error::RmpParse(e.to_string()))?;
+    bytes.extend(timestamp.to_be_bytes());
+    if let Some(vault_address) = vault_address {
+        bytes.push(1);
+        bytes.extend(vault_address.to_fixed_bytes());
+    } else {
+        bytes.push(0);
+    }
+    if let Some(expires_after) = expires_after {
+        bytes.push(0);
+        bytes.extend(expires_after.to_be_bytes());
+    }
+    Ok(H256(ethers::utils::keccak256(bytes)))
+}
+
+impl<S: HyperliquidSigner + Clone> ExchangeClient<S> {
+    /// A copy of this client that trades and transfers on behalf of `sub_account`. Actions are
+    /// still signed by the master wallet and carry the sub-account as `vaultAddress`.
+    pub fn for_sub_account(&self, sub_account: H160) -> ExchangeClient<S> {
+        ExchangeClient {
+            vault_address: Some(sub_account),
+            ..self.clone()
+        }
+    }
+}
+
+impl<S: HyperliquidSigner + From<LocalWallet>> ExchangeClient<S> {
+    /// Rotates the agent this client signs with: `master` approves a fresh agent under
+    /// `agent_name`, and the new agent, converted into `S`, becomes this client's wallet. Returns
+    /// the new agent's private key so it can be persisted. The wallet is left unchanged if the
+    /// approval is rejected.
+    ///
+    /// There is no separate revoke action. A master holds at most one agent per name, plus one
+    /// unnamed agent, so approving the new agent is what revokes the one previously approved
+    /// under `agent_name`. [`InfoClient::extra_agents`] lists the named agents still in place.
+    ///
+    /// [`InfoClient::extra_agents`]: crate::InfoClient::extra_agents
+    pub async fn rotate_agent<M: HyperliquidSigner>(
+        &mut self,
+        agent_name: Option<&str>,
+        master: &M,
+    ) -> Result<(String, ExchangeResponseStatus)> {
+        let (key, response) = self.send_approve_agent(agent_name, master).await?;
+        if let ExchangeResponseStatus::Ok(_) = response {
+            self.wallet = key
+                .parse::<LocalWallet>()
+                .map_err(|e| Error::PrivateKeyParse(e.to_string()))?
+                .into();
         }
-        Ok(H256(ethers::utils::keccak256(bytes)))
+        Ok((key, response))
     }
 }
 
-impl ExchangeClient {
+impl<S: HyperliquidSigner> ExchangeClient<S> {
     pub async fn new(
         client: Option<Client>,
-        wallet: LocalWallet,
+        wallet: S,
         base_url: Option<BaseUrl>,
         meta: Option<Meta>,
         vault_address: Option<H160>,
-    ) -> Result<ExchangeClient> {
+    ) -> Result<ExchangeClient<S>> {
         let client = client.unwrap_or_default();
         let base_url = base_url.unwrap_or(BaseUrl::Mainnet);
 
-        let info = InfoClient::new(None, Some(base_url)).await?;
+        let info = InfoClient::new(None, Some(base_url.clone())).await?;
         let meta = if let Some(meta) = meta {
             meta
         } else {
             info.meta().await?
         };
 
+        let spot_meta = info.spot_meta().await?;
+        Ok(Self::from_meta(
+            Some(client),
+            wallet,
+            Some(base_url),
+            meta,
+            spot_meta,
+            vault_address,
+        ))
+    }
+
+    /// Builds a client from cached `Meta` and `SpotMeta` without any network access, e.g. to
+    /// sign actions with [`Self::sign_action`] on an offline machine.
+    pub fn from_meta(
+        client: Option<Client>,
+        wallet: S,
+        base_url: Option<BaseUrl>,
+        meta: Meta,
+        spot_meta: SpotMeta,
+        vault_address: Option<H160>,
+    ) -> ExchangeClient<S> {
+        let client = client.unwrap_or_default();
+        let base_url = base_url.unwrap_or(BaseUrl::Mainnet);
+
         let mut coin_to_asset = HashMap::new();
         for (asset_ind, asset) in meta.universe.iter().enumerate() {
             coin_to_asset.insert(asset.name.clone(), asset_ind as u32);
         }
 
-        coin_to_asset = info
-            .spot_meta()
-            .await?
-            .add_pair_and_name_to_index_map(coin_to_asset);
+        coin_to_asset = spot_meta.add_pair_and_name_to_index_map(coin_to_asset);
+        let order_normalizer = OrderNormalizer::new(&meta, &spot_meta);
 
-        Ok(ExchangeClient {
+        ExchangeClient {
             wallet,
             meta,
             vault_address,
             http_client: HttpClient {
                 client,
-                base_url: base_url.get_url(),
+                base_url,
+                config: HttpConfig::default(),
+                rate_limiter: RateLimiter::shared(),
             },
             coin_to_asset,
-        })
+            order_normalizer,
+            normalize_orders: false,
+            validate_orders: false,
+            nonce_provider: InMemoryNonceProvider::shared(),
+            expires_after: None,
+        }
+    }
+
+    /// Replaces the provider of nonces, which by default is shared by every client of this
+    /// process.
+    pub fn with_nonce_provider(mut self, nonce_provider: Arc<dyn NonceProvider>) -> Self {
+        self.nonce_provider = nonce_provider;
+        self
+    }
+
+    /// Makes the exchange reject the actions of this client after `expires_after`, a time in
+    /// milliseconds. A single action can expire with [`Self::sign_action`] instead.
+    pub fn expiring_after(mut self, expires_after: u64) -> Self {
+        self.expires_after = Some(expires_after);
+        self
+    }
+
+    /// Replaces the timeouts and retry policies of `/info` and `/exchange` requests.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.http_client.config = config;
+        self
+    }
+
+    /// Replaces the budget of request weight, e.g. to share one limiter in
+    /// [`RateLimitMode::Wait`](crate::RateLimitMode::Wait) with an `InfoClient`.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.http_client.rate_limiter = rate_limiter;
+        self
+    }
+
+    fn hyperliquid_chain(&self) -> String {
+        if self.http_client.is_mainnet() {
+            "Mainnet".to_string()
+        } else {
+            "Testnet".to_string()
+        }
+    }
+
+    fn next_nonce(&self, signer: H160) -> Result<u64> {
+        self.nonce_provider.next_nonce(signer)
+    }
+
+    fn convert_order(&self, order: ClientOrderRequest) -> Result<OrderRequest> {
+        let order = if self.normalize_orders {
+            self.order_normalizer.normalize(order)?
+        } else {
+            order
+        };
+        if self.validate_orders {
+            order
+                .validate(&OrderValidationContext::new(&self.coin_to_asset))
+                .map_err(Error::OrderValidation)?;
+        }
+        order.convert(&self.coin_to_asset)
+    }
+
+    /// Loads the universes of builder-deployed perp dexs so that their coins can be traded and
+    /// cancelled as `dex:COIN`. `None` loads every dex listed by `perpDexs`.
+    pub async fn with_perp_dexs(mut self, dexs: Option<&[&str]>) -> Result<ExchangeClient<S>> {
+        let info = InfoClient::with_http_client(self.http_client.clone());
+        let perp_dexs = info.perp_dexs().await?;
+
+        for (dex_index, perp_dex) in perp_dexs.iter().enumerate() {
+            let Some(perp_dex) = perp_dex else {
+                continue;
+            };
+            if dexs.is_some_and(|dexs| !dexs.contains(&perp_dex.name.as_str())) {
+                continue;
+            }
+            let meta = info.meta_for_dex(&perp_dex.name).await?;
+            self.coin_to_asset =
+                meta.add_perp_dex_to_index_map(&perp_dex.name, dex_index, self.coin_to_asset);
+            self.order_normalizer.add_perp_dex(&perp_dex.name, &meta);
+        }
+        Ok(self)
     }
 
-    async fn post(
+    /// Signs `action` with [`Self::sign_action`] and sends it.
+    async fn send_action<T: for<'a> Deserialize<'a>>(
         &self,
-        action: serde_json::Value,
-        signature: Signature,
+        action: Actions,
         nonce: u64,
-    ) -> Result<ExchangeResponseStatus> {
-        let exchange_payload = ExchangePayload {
+        wallet: &S,
+    ) -> Result<T> {
+        let payload = self.sign_action(action, nonce, None, Some(wallet)).await?;
+        self.post_signed(&payload).await
+    }
+
+    /// Sends a payload signed with [`Self::sign_action`], possibly by another client.
+    pub async fn post_signed<T: for<'a> Deserialize<'a>>(
+        &self,
+        payload: &ExchangePayload,
+    ) -> Result<T> {
+        payload.post(&self.http_client).await
+    }
+
+    /// Signs `action` without sending it. L1 actions are signed with `nonce`, while user-signed
+    /// actions such as `UsdSend` carry their own nonce in the action, which `nonce` has to match.
+    /// `expires_after` overrides the expiry of the client for this action.
+    pub async fn sign_action(
+        &self,
+        action: Actions,
+        nonce: u64,
+        expires_after: Option<u64>,
+        wallet: Option<&S>,
+    ) -> Result<ExchangePayload> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        self.sign_action_with(action, nonce, expires_after, wallet)
+            .await
+    }
+
+    /// [`Self::sign_action`] with a signer of another type, such as the master wallet approving
+    /// an agent.
+    async fn sign_action_with<W: HyperliquidSigner>(
+        &self,
+        action: Actions,
+        nonce: u64,
+        expires_after: Option<u64>,
+        wallet: &W,
+    ) -> Result<ExchangePayload> {
+        let expires_after = expires_after.or(self.expires_after);
+        let is_mainnet = self.http_client.is_mainnet();
+
+        let action_nonce = match &action {
+            Actions::UsdSend(usd_send) => Some(usd_send.time),
+            Actions::Withdraw3(withdraw) => Some(withdraw.time),
+            Actions::SpotSend(spot_send) => Some(spot_send.time),
+            Actions::ApproveAgent(approve_agent) => Some(approve_agent.nonce),
+            Actions::CDeposit(c_deposit) => Some(c_deposit.nonce),
+            Actions::CWithdraw(c_withdraw) => Some(c_withdraw.nonce),
+            Actions::TokenDelegate(token_delegate) => Some(token_delegate.nonce),
+            Actions::ConvertToMultiSigUser(convert) => Some(convert.nonce),
+            Actions::ApproveBuilderFee(approve_builder_fee) => Some(approve_builder_fee.nonce),
+            _ => None,
+        };
+        if let Some(action_nonce) = action_nonce.filter(|&action_nonce| action_nonce != nonce) {
+            return Err(Error::Nonce(format!(
+                "nonce {nonce} differs from the nonce {action_nonce} of the user-signed action"
+            )));
+        }
+
+        let (signature, vault_address) = match &action {
+            Actions::UsdSend(usd_send) => {
+                (wallet.sign_typed_data(usd_send).await?, self.vault_address)
+            }
+            Actions::Withdraw3(withdraw) => {
+                (wallet.sign_typed_data(withdraw).await?, self.vault_address)
+            }
+            Actions::SpotSend(spot_send) => {
+                (wallet.sign_typed_data(spot_send).await?, self.vault_address)
+            }
+            Actions::ApproveAgent(approve_agent) => (
+                wallet.sign_typed_data(approve_agent).await?,
+                self.vault_address,
+            ),
+            Actions::CDeposit(c_deposit) => {
+                (wallet.sign_typed_data(c_deposit).await?, self.vault_address)
+            }
+            Actions::CWithdraw(c_withdraw) => (
+                wallet.sign_typed_data(c_withdraw).await?,
+                self.vault_address,
+            ),
+            Actions::TokenDelegate(token_delegate) => (
+                wallet.sign_typed_data(token_delegate).await?,
+                self.vault_address,
+            ),
+            Actions::ConvertToMultiSigUser(convert) => {
+                (wallet.sign_typed_data(convert).await?, self.vault_address)
+            }
+            Actions::ApproveBuilderFee(approve_builder_fee) => (
+                wallet.sign_typed_data(approve_builder_fee).await?,
+                self.vault_address,
+            ),
+            Actions::MultiSig(multi_sig) => {
+                // the envelope hash covers the action without its type tag
+                let multi_sig_action_hash =
+                    action_hash_with_expiry(multi_sig, nonce, self.vault_address, expires_after)?;
+                let envelope = SendMultiSig {
+                    signature_chain_id: 421614.into(),
+                    hyperliquid_chain: self.hyperliquid_chain(),
+                    multi_sig_action_hash,
+                    nonce,
+                };
+                (wallet.sign_typed_data(&envelope).await?, self.vault_address)
+            }
+            // sub-account, vault and deploy management is never done on behalf of a vault
+            Actions::CreateSubAccount(_)
+            | Actions::SubAccountTransfer(_)
+            | Actions::SubAccountSpotTransfer(_)
+            | Actions::CreateVault(_)
+            | Actions::VaultModify(_)
+            | Actions::VaultDistribute(_)
+            | Actions::SpotDeploy(_) => {
+                let connection_id = action.hash_with_expiry(nonce, None, expires_after)?;
+                (
+                    wallet.sign_l1_action(connection_id, is_mainnet).await?,
+                    None,
+                )
+            }
+            _ => {
+                let connection_id =
+                    action.hash_with_expiry(nonce, self.vault_address, expires_after)?;
+                (
+                    wallet.sign_l1_action(connection_id, is_mainnet).await?,
+                    self.vault_address,
+                )
+            }
+        };
+        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+
+        Ok(ExchangePayload {
             action,
             signature,
             nonce,
-            vault_address: self.vault_address,
-        };
-        let res = serde_json::to_string(&exchange_payload)
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
-        debug!("Sending request {res:?}");
-
-        let output = &self
-            .http_client
-            .post("/exchange", res)
-            .await
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
-        serde_json::from_str(output).map_err(|e| Error::JsonParse(e.to_string()))
+            vault_address,
+            expires_after,
+        })
     }
 
     pub async fn usdc_transfer(
         &self,
         amount: &str,
         destination: &str,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         let wallet = wallet.unwrap_or(&self.wallet);
-        let hyperliquid_chain = if self.http_client.is_mainnet() {
-            "Mainnet".to_string()
-        } else {
-            "Testnet".to_string()
-        };
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.usdc_transfer_action(amount, destination, timestamp);
+        self.send_action(action, timestamp, wallet).await
+    }
 
-        let timestamp = next_nonce();
-        let usd_send = UsdSend {
+    /// Builds a `usdSend` action for [`Self::sign_action`], which is signed with `time` as
+    /// nonce.
+    pub fn usdc_transfer_action(&self, amount: &str, destination: &str, time: u64) -> Actions {
+        Actions::UsdSend(UsdSend {
             signature_chain_id: 421614.into(),
-            hyperliquid_chain,
+            hyperliquid_chain: self.hyperliquid_chain(),
             destination: destination.to_string(),
             amount: amount.to_string(),
-            time: timestamp,
-        };
-        let signature = sign_typed_data(&usd_send, wallet)?;
-        let action = serde_json::to_value(Actions::UsdSend(usd_send))
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
-
-        self.post(action, signature, timestamp).await
+            time,
+        })
     }
 
     pub async fn class_transfer(
         &self,
         usdc: f64,
         to_perp: bool,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
-        // payload expects usdc without decimals
-        let usdc = (usdc * 1e6).round() as u64;
         let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.class_transfer_action(usdc, to_perp);
+        self.send_action(action, timestamp, wallet).await
+    }
 
-        let timestamp = next_nonce();
-
-        let action = Actions::SpotUser(SpotUser {
+    /// Builds a transfer of `usdc` between the spot and perp balances for [`Self::sign_action`].
+    pub fn class_transfer_action(&self, usdc: f64, to_perp: bool) -> Actions {
+        // payload expects usdc without decimals
+        let usdc = (usdc * 1e6).round() as u64;
+        Actions::SpotUser(SpotUser {
             class_transfer: ClassTransfer { usdc, to_perp },
-        });
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
-
-        self.post(action, signature, timestamp).await
+        })
     }
 
     pub async fn vault_transfer(
@@ -205,32 +629,36 @@ impl ExchangeClient {
         is_deposit: bool,
         usd: String,
         vault_address: Option<H160>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.vault_transfer_action(is_deposit, usd, vault_address)?;
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a deposit into or withdrawal from a vault for [`Self::sign_action`]. The client's
+    /// own vault address takes precedence over `vault_address`.
+    pub fn vault_transfer_action(
+        &self,
+        is_deposit: bool,
+        usd: String,
+        vault_address: Option<H160>,
+    ) -> Result<Actions> {
         let vault_address = self
             .vault_address
             .or(vault_address)
             .ok_or(Error::VaultAddressNotFound)?;
-        let wallet = wallet.unwrap_or(&self.wallet);
-
-        let timestamp = next_nonce();
-
-        let action = Actions::VaultTransfer(VaultTransfer {
+        Ok(Actions::VaultTransfer(VaultTransfer {
             vault_address,
             is_deposit,
             usd,
-        });
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
-
-        self.post(action, signature, timestamp).await
+        }))
     }
 
     pub async fn market_open(
         &self,
-        params: MarketOrderParams<'_>,
+        params: MarketOrderParams<'_, S>,
     ) -> Result<ExchangeResponseStatus> {
         let slippage = params.slippage.unwrap_or(0.05); // Default 5% slippage
         let (px, sz_decimals) = self
@@ -242,11 +670,9 @@ impl ExchangeClient {
             is_buy: params.is_buy,
             reduce_only: false,
             limit_px: px,
-            sz: round_to_decimals(params.sz, sz_decimals),
+            sz: params.sz.round_dp(sz_decimals),
             cloid: params.cloid,
-            order_type: ClientOrder::Limit(ClientLimit {
-                tif: "Ioc".to_string(),
-            }),
+            order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Ioc }),
         };
 
         self.order(order, params.wallet).await
@@ -254,7 +680,7 @@ impl ExchangeClient {
 
     pub async fn market_open_with_builder(
         &self,
-        params: MarketOrderParams<'_>,
+        params: MarketOrderParams<'_, S>,
         builder: BuilderInfo,
     ) -> Result<ExchangeResponseStatus> {
         let slippage = params.slippage.unwrap_or(0.05); // Default 5% slippage
@@ -267,11 +693,9 @@ impl ExchangeClient {
             is_buy: params.is_buy,
             reduce_only: false,
             limit_px: px,
-            sz: round_to_decimals(params.sz, sz_decimals),
+            sz: params.sz.round_dp(sz_decimals),
             cloid: params.cloid,
-            order_type: ClientOrder::Limit(ClientLimit {
-                tif: "Ioc".to_string(),
-            }),
+            order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Ioc }),
         };
 
         self.order_with_builder(order, params.wallet, builder).await
@@ -279,90 +703,75 @@ impl ExchangeClient {
 
     pub async fn market_close(
         &self,
-        params: MarketCloseParams<'_>,
+        params: MarketCloseParams<'_, S>,
     ) -> Result<ExchangeResponseStatus> {
         let slippage = params.slippage.unwrap_or(0.05); // Default 5% slippage
         let wallet = params.wallet.unwrap_or(&self.wallet);
 
-        let base_url = match self.http_client.base_url.as_str() {
-            "https://api.hyperliquid.xyz" => BaseUrl::Mainnet,
-            "https://api.hyperliquid-testnet.xyz" => BaseUrl::Testnet,
-            _ => return Err(Error::GenericRequest("Invalid base URL".to_string())),
-        };
-        let info_client = InfoClient::new(None, Some(base_url)).await?;
-        let user_state = info_client.user_state(wallet.address()).await?;
-
-        let position = user_state
-            .asset_positions
-            .iter()
-            .find(|p| p.position.coin == params.asset)
-            .ok_or(Error::AssetNotFound)?;
-
-        let szi = position
-            .position
-            .szi
-            .parse::<f64>()
-            .map_err(|_| Error::FloatStringParse)?;
+        let szi = self.position_size(params.asset, wallet.address()).await?;
 
         let (px, sz_decimals) = self
-            .calculate_slippage_price(params.asset, szi < 0.0, slippage, params.px)
+            .calculate_slippage_price(params.asset, szi.is_negative(), slippage, params.px)
             .await?;
 
-        let sz = round_to_decimals(params.sz.unwrap_or_else(|| szi.abs()), sz_decimals);
+        let sz = params.sz.unwrap_or_else(|| szi.abs()).round_dp(sz_decimals);
 
         let order = ClientOrderRequest {
             asset: params.asset.to_string(),
-            is_buy: szi < 0.0,
+            is_buy: szi.is_negative(),
             reduce_only: true,
             limit_px: px,
             sz,
             cloid: params.cloid,
-            order_type: ClientOrder::Limit(ClientLimit {
-                tif: "Ioc".to_string(),
-            }),
+            order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Ioc }),
         };
 
         self.order(order, Some(wallet)).await
     }
 
-    async fn calculate_slippage_price(
-        &self,
-        asset: &str,
-        is_buy: bool,
-        slippage: f64,
-        px: Option<f64>,
-    ) -> Result<(f64, u32)> {
-        let base_url = match self.http_client.base_url.as_str() {
-            "https://api.hyperliquid.xyz" => BaseUrl::Mainnet,
-            "https://api.hyperliquid-testnet.xyz" => BaseUrl::Testnet,
-            _ => return Err(Error::GenericRequest("Invalid base URL".to_string())),
+    /// Signed size of the open position of `user` in `coin`.
+    async fn position_size(&self, coin: &str, user: H160) -> Result<Decimal> {
+        let info_client = InfoClient::with_http_client(self.http_client.clone());
+        let dex = coin_perp_dex(coin);
+        let user_state = if dex.is_empty() {
+            info_client.user_state(user).await?
+        } else {
+            info_client.user_state_for_dex(user, dex).await?
         };
-        let info_client = InfoClient::new(None, Some(base_url)).await?;
-        let meta = info_client.meta().await?;
 
-        let asset_meta = meta
-            .universe
+        let position = user_state
+            .asset_positions
             .iter()
-            .find(|a| a.name == asset)
+            .find(|p| perp_dex_coin(dex, &p.position.coin) == coin)
             .ok_or(Error::AssetNotFound)?;
+        Ok(position.position.szi)
+    }
 
-        let sz_decimals = asset_meta.sz_decimals;
-        let max_decimals: u32 = if self.coin_to_asset[asset] < 10000 {
-            6
-        } else {
-            8
-        };
-        let price_decimals = max_decimals.saturating_sub(sz_decimals);
+    async fn calculate_slippage_price(
+        &self,
+        asset: &str,
+        is_buy: bool,
+        slippage: f64,
+        px: Option<Decimal>,
+    ) -> Result<(Decimal, u32)> {
+        let info_client = InfoClient::with_http_client(self.http_client.clone());
+        let precision = self.order_normalizer.precision(asset)?;
 
         let px = if let Some(px) = px {
             px
         } else {
-            let all_mids = info_client.all_mids().await?;
+            let dex = coin_perp_dex(asset);
+            let all_mids = if dex.is_empty() {
+                info_client.all_mids().await?
+            } else {
+                info_client.all_mids_for_dex(dex).await?
+            };
             all_mids
-                .get(asset)
+                .iter()
+                .find(|(coin, _)| perp_dex_coin(dex, coin) == asset)
                 .ok_or(Error::AssetNotFound)?
-                .parse::<f64>()
-                .map_err(|_| Error::FloatStringParse)?
+                .1
+                .parse::<Decimal>()?
         };
 
         debug!("px before slippage: {px:?}");
@@ -371,19 +780,22 @@ impl ExchangeClient {
         } else {
             1.0 - slippage
         };
-        let px = px * slippage_factor;
+        let slippage_factor = Decimal::try_from(slippage_factor)?;
+        let px = px
+            .checked_mul(slippage_factor)
+            .ok_or_else(|| Error::DecimalOverflow(format!("{px} * {slippage_factor}")))?;
 
         // Round to the correct number of decimal places and significant figures
-        let px = round_to_significant_and_decimal(px, 5, price_decimals);
+        let px = precision.round_price(px, RoundingMode::Nearest)?;
 
         debug!("px after slippage: {px:?}");
-        Ok((px, sz_decimals))
+        Ok((px, precision.sz_decimals))
     }
 
     pub async fn order(
         &self,
         order: ClientOrderRequest,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         self.bulk_order(vec![order], wallet).await
     }
@@ -391,86 +803,107 @@ impl ExchangeClient {
     pub async fn order_with_builder(
         &self,
         order: ClientOrderRequest,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
         builder: BuilderInfo,
     ) -> Result<ExchangeResponseStatus> {
         self.bulk_order_with_builder(vec![order], wallet, builder)
             .await
     }
 
-    pub async fn bulk_order(
+    /// Places an entry order together with optional take profit and stop loss triggers as one
+    /// `normalTpsl` group. See [`ClientOrderRequest::with_tpsl`].
+    pub async fn order_with_tpsl(
         &self,
-        orders: Vec<ClientOrderRequest>,
-        wallet: Option<&LocalWallet>,
+        entry: ClientOrderRequest,
+        tp: Option<Decimal>,
+        sl: Option<Decimal>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.wallet);
-        let timestamp = next_nonce();
-
-        let mut transformed_orders = Vec::new();
+        self.bulk_order_with_grouping(entry.with_tpsl(tp, sl), Grouping::NormalTpsl, wallet)
+            .await
+    }
 
-        for order in orders {
-            transformed_orders.push(order.convert(&self.coin_to_asset)?);
-        }
+    /// Attaches take profit and stop loss triggers to the whole open position in `coin` as one
+    /// `positionTpsl` group. See [`ClientOrderRequest::position_tpsl`].
+    pub async fn position_tpsl(
+        &self,
+        coin: &str,
+        tp: Option<Decimal>,
+        sl: Option<Decimal>,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let szi = self.position_size(coin, wallet.address()).await?;
+        let orders = ClientOrderRequest::position_tpsl(coin, szi, tp, sl);
+        self.bulk_order_with_grouping(orders, Grouping::PositionTpsl, Some(wallet))
+            .await
+    }
 
-        let action = Actions::Order(BulkOrder {
-            orders: transformed_orders,
-            grouping: "na".to_string(),
-            builder: None,
-        });
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+    pub async fn bulk_order(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.bulk_order_with_grouping(orders, Grouping::Na, wallet)
+            .await
+    }
 
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
-        self.post(action, signature, timestamp).await
+    pub async fn bulk_order_with_grouping(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        grouping: Grouping,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.send_bulk_order(orders, grouping, None, wallet).await
     }
 
     pub async fn bulk_order_with_builder(
         &self,
         orders: Vec<ClientOrderRequest>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
         mut builder: BuilderInfo,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.wallet);
-        let timestamp = next_nonce();
-
         builder.builder = builder.builder.to_lowercase();
+        self.send_bulk_order(orders, Grouping::Na, Some(builder), wallet)
+            .await
+    }
+
+    async fn send_bulk_order(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        grouping: Grouping,
+        builder: Option<BuilderInfo>,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.order_action(orders, grouping, builder)?;
+        self.send_action(action, timestamp, wallet).await
+    }
 
+    /// Builds an order action for [`Self::sign_action`], applying `normalize_orders` and
+    /// `validate_orders`.
+    pub fn order_action(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        grouping: Grouping,
+        builder: Option<BuilderInfo>,
+    ) -> Result<Actions> {
         let mut transformed_orders = Vec::new();
 
         for order in orders {
-            transformed_orders.push(order.convert(&self.coin_to_asset)?);
+            transformed_orders.push(self.convert_order(order)?);
         }
 
-        let action = Actions::Order(BulkOrder {
+        Ok(Actions::Order(BulkOrder {
             orders: transformed_orders,
-            grouping: "na".to_string(),
-            builder: Some(builder),
-        });
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
-
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
-        self.post(action, signature, timestamp).await
-    }
-
-    pub async fn cancel(
-        &self,
-        cancel: ClientCancelRequest,
-        wallet: Option<&LocalWallet>,
-    ) -> Result<ExchangeResponseStatus> {
-        self.bulk_cancel(vec![cancel], wallet).await
+            grouping,
+            builder,
+        }))
     }
 
-    pub async fn bulk_cancel(
-        &self,
-        cancels: Vec<ClientCancelRequest>,
-        wallet: Option<&LocalWallet>,
-    ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.wallet);
-        let timestamp = next_nonce();
-
+    /// Builds a cancel action for [`Self::sign_action`].
+    pub fn cancel_action(&self, cancels: Vec<ClientCancelRequest>) -> Result<Actions> {
         let mut transformed_cancels = Vec::new();
         for cancel in cancels.into_iter() {
             let &asset = self
@@ -483,22 +916,34 @@ impl ExchangeClient {
             });
         }
 
-        let action = Actions::Cancel(BulkCancel {
+        Ok(Actions::Cancel(BulkCancel {
             cancels: transformed_cancels,
-        });
-        let connection_id = action.hash(timestamp, self.vault_address)?;
+        }))
+    }
 
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+    pub async fn cancel(
+        &self,
+        cancel: ClientCancelRequest,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.bulk_cancel(vec![cancel], wallet).await
+    }
 
-        self.post(action, signature, timestamp).await
+    pub async fn bulk_cancel(
+        &self,
+        cancels: Vec<ClientCancelRequest>,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.cancel_action(cancels)?;
+        self.send_action(action, timestamp, wallet).await
     }
 
     pub async fn modify(
         &self,
         modify: ClientModifyRequest,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         self.bulk_modify(vec![modify], wallet).await
     }
@@ -506,35 +951,34 @@ impl ExchangeClient {
     pub async fn bulk_modify(
         &self,
         modifies: Vec<ClientModifyRequest>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         let wallet = wallet.unwrap_or(&self.wallet);
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.modify_action(modifies)?;
+        self.send_action(action, timestamp, wallet).await
+    }
 
+    /// Builds a batch modify action for [`Self::sign_action`], applying `normalize_orders` and
+    /// `validate_orders` to the new orders.
+    pub fn modify_action(&self, modifies: Vec<ClientModifyRequest>) -> Result<Actions> {
         let mut transformed_modifies = Vec::new();
         for modify in modifies.into_iter() {
             transformed_modifies.push(ModifyRequest {
                 oid: modify.oid,
-                order: modify.order.convert(&self.coin_to_asset)?,
+                order: self.convert_order(modify.order)?,
             });
         }
 
-        let action = Actions::BatchModify(BulkModify {
+        Ok(Actions::BatchModify(BulkModify {
             modifies: transformed_modifies,
-        });
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
-
-        self.post(action, signature, timestamp).await
+        }))
     }
 
     pub async fn cancel_by_cloid(
         &self,
         cancel: ClientCancelRequestCloid,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         self.bulk_cancel_by_cloid(vec![cancel], wallet).await
     }
@@ -542,11 +986,19 @@ impl ExchangeClient {
     pub async fn bulk_cancel_by_cloid(
         &self,
         cancels: Vec<ClientCancelRequestCloid>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         let wallet = wallet.unwrap_or(&self.wallet);
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.cancel_by_cloid_action(cancels)?;
+        self.send_action(action, timestamp, wallet).await
+    }
 
+    /// Builds a cancel by client order id action for [`Self::sign_action`].
+    pub fn cancel_by_cloid_action(
+        &self,
+        cancels: Vec<ClientCancelRequestCloid>,
+    ) -> Result<Actions> {
         let mut transformed_cancels: Vec<CancelRequestCloid> = Vec::new();
         for cancel in cancels.into_iter() {
             let &asset = self
@@ -559,16 +1011,63 @@ impl ExchangeClient {
             });
         }
 
-        let action = Actions::CancelByCloid(BulkCancelCloid {
+        Ok(Actions::CancelByCloid(BulkCancelCloid {
             cancels: transformed_cancels,
-        });
+        }))
+    }
 
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+    pub async fn twap_order(
+        &self,
+        twap: ClientTwapRequest,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.twap_order_action(twap)?;
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a TWAP order action for [`Self::sign_action`], rounding the size when
+    /// `normalize_orders` is set.
+    pub fn twap_order_action(&self, twap: ClientTwapRequest) -> Result<Actions> {
+        let &asset = self
+            .coin_to_asset
+            .get(&twap.asset)
+            .ok_or(Error::AssetNotFound)?;
+        let sz = if self.normalize_orders {
+            self.order_normalizer.round_size(&twap.asset, twap.sz)?
+        } else {
+            twap.sz
+        };
+
+        Ok(Actions::TwapOrder(TwapOrder {
+            twap: TwapRequest {
+                asset,
+                is_buy: twap.is_buy,
+                sz: sz.to_string(),
+                reduce_only: twap.reduce_only,
+                minutes: twap.minutes,
+                randomize: twap.randomize,
+            },
+        }))
+    }
+
+    pub async fn twap_cancel(
+        &self,
+        asset: &str,
+        twap_id: u64,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.twap_cancel_action(asset, twap_id)?;
+        self.send_action(action, timestamp, wallet).await
+    }
 
-        self.post(action, signature, timestamp).await
+    /// Builds a TWAP cancel action for [`Self::sign_action`].
+    pub fn twap_cancel_action(&self, asset: &str, twap_id: u64) -> Result<Actions> {
+        let &asset = self.coin_to_asset.get(asset).ok_or(Error::AssetNotFound)?;
+        Ok(Actions::TwapCancel(TwapCancel { asset, twap_id }))
     }
 
     pub async fn update_leverage(
@@ -576,56 +1075,78 @@ impl ExchangeClient {
         leverage: u32,
         coin: &str,
         is_cross: bool,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.update_leverage_action(leverage, coin, is_cross)?;
+        self.send_action(action, timestamp, wallet).await
+    }
 
-        let timestamp = next_nonce();
-
+    /// Builds an update leverage action for [`Self::sign_action`].
+    pub fn update_leverage_action(
+        &self,
+        leverage: u32,
+        coin: &str,
+        is_cross: bool,
+    ) -> Result<Actions> {
         let &asset_index = self.coin_to_asset.get(coin).ok_or(Error::AssetNotFound)?;
-        let action = Actions::UpdateLeverage(UpdateLeverage {
+        Ok(Actions::UpdateLeverage(UpdateLeverage {
             asset: asset_index,
             is_cross,
             leverage,
-        });
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
-
-        self.post(action, signature, timestamp).await
+        }))
     }
 
     pub async fn update_isolated_margin(
         &self,
         amount: f64,
         coin: &str,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.update_isolated_margin_action(amount, coin)?;
+        self.send_action(action, timestamp, wallet).await
+    }
 
+    /// Builds an action adding `amount` USDC to (or removing it from, when negative) the
+    /// isolated margin of `coin` for [`Self::sign_action`].
+    pub fn update_isolated_margin_action(&self, amount: f64, coin: &str) -> Result<Actions> {
         let amount = (amount * 1_000_000.0).round() as i64;
-        let timestamp = next_nonce();
-
         let &asset_index = self.coin_to_asset.get(coin).ok_or(Error::AssetNotFound)?;
-        let action = Actions::UpdateIsolatedMargin(UpdateIsolatedMargin {
+        Ok(Actions::UpdateIsolatedMargin(UpdateIsolatedMargin {
             asset: asset_index,
             is_buy: true,
             ntli: amount,
-        });
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
-
-        self.post(action, signature, timestamp).await
+        }))
     }
 
     pub async fn approve_agent(
         &self,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
+    ) -> Result<(String, ExchangeResponseStatus)> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        self.send_approve_agent(None, wallet).await
+    }
+
+    /// Like `approve_agent`, but approves the new agent under `agent_name`. Approving a name
+    /// again replaces the agent that held it, as approving an unnamed agent replaces the
+    /// previous unnamed one.
+    pub async fn approve_named_agent(
+        &self,
+        agent_name: &str,
+        wallet: Option<&S>,
     ) -> Result<(String, ExchangeResponseStatus)> {
         let wallet = wallet.unwrap_or(&self.wallet);
+        self.send_approve_agent(Some(agent_name), wallet).await
+    }
+
+    async fn send_approve_agent<W: HyperliquidSigner>(
+        &self,
+        agent_name: Option<&str>,
+        wallet: &W,
+    ) -> Result<(String, ExchangeResponseStatus)> {
         let key = H256::from(generate_random_key()?).encode_hex()[2..].to_string();
 
         let address = key
@@ -633,52 +1154,193 @@ impl ExchangeClient {
             .map_err(|e| Error::PrivateKeyParse(e.to_string()))?
             .address();
 
-        let hyperliquid_chain = if self.http_client.is_mainnet() {
-            "Mainnet".to_string()
-        } else {
-            "Testnet".to_string()
-        };
+        let nonce = self.next_nonce(wallet.address())?;
+        let action = self.approve_agent_action(address, agent_name, nonce);
+        let payload = self.sign_action_with(action, nonce, None, wallet).await?;
+        Ok((key, self.post_signed(&payload).await?))
+    }
 
-        let nonce = next_nonce();
-        let approve_agent = ApproveAgent {
+    /// Builds an `approveAgent` action for [`Self::sign_action`], which is signed with `nonce`.
+    /// The agent key is generated by the caller, e.g. on the machine that will use it.
+    pub fn approve_agent_action(
+        &self,
+        agent_address: H160,
+        agent_name: Option<&str>,
+        nonce: u64,
+    ) -> Actions {
+        Actions::ApproveAgent(ApproveAgent {
             signature_chain_id: 421614.into(),
-            hyperliquid_chain,
-            agent_address: address,
-            agent_name: None,
+            hyperliquid_chain: self.hyperliquid_chain(),
+            agent_address,
+            agent_name: agent_name.map(str::to_string),
             nonce,
-        };
-        let signature = sign_typed_data(&approve_agent, wallet)?;
-        let action = serde_json::to_value(Actions::ApproveAgent(approve_agent))
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
-        Ok((key, self.post(action, signature, nonce).await?))
+        })
     }
 
     pub async fn withdraw_from_bridge(
         &self,
         amount: &str,
         destination: &str,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         let wallet = wallet.unwrap_or(&self.wallet);
-        let hyperliquid_chain = if self.http_client.is_mainnet() {
-            "Mainnet".to_string()
-        } else {
-            "Testnet".to_string()
-        };
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.withdraw_from_bridge_action(amount, destination, timestamp);
+        self.send_action(action, timestamp, wallet).await
+    }
 
-        let timestamp = next_nonce();
-        let withdraw = Withdraw3 {
+    /// Builds a `withdraw3` action for [`Self::sign_action`], which is signed with `time` as
+    /// nonce.
+    pub fn withdraw_from_bridge_action(
+        &self,
+        amount: &str,
+        destination: &str,
+        time: u64,
+    ) -> Actions {
+        Actions::Withdraw3(Withdraw3 {
             signature_chain_id: 421614.into(),
-            hyperliquid_chain,
+            hyperliquid_chain: self.hyperliquid_chain(),
             destination: destination.to_string(),
             amount: amount.to_string(),
-            time: timestamp,
-        };
-        let signature = sign_typed_data(&withdraw, wallet)?;
-        let action = serde_json::to_value(Actions::Withdraw3(withdraw))
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
+            time,
+        })
+    }
+
+    /// Moves `wei` of HYPE (8 decimals) from the spot balance into staking.
+    pub async fn c_deposit(&self, wei: u64, wallet: Option<&S>) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.c_deposit_action(wei, timestamp);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `cDeposit` action for [`Self::sign_action`], which is signed with `nonce`.
+    pub fn c_deposit_action(&self, wei: u64, nonce: u64) -> Actions {
+        Actions::CDeposit(CDeposit {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            wei,
+            nonce,
+        })
+    }
+
+    /// Moves `wei` of undelegated HYPE from staking back to the spot balance.
+    pub async fn c_withdraw(&self, wei: u64, wallet: Option<&S>) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.c_withdraw_action(wei, timestamp);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `cWithdraw` action for [`Self::sign_action`], which is signed with `nonce`.
+    pub fn c_withdraw_action(&self, wei: u64, nonce: u64) -> Actions {
+        Actions::CWithdraw(CWithdraw {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            wei,
+            nonce,
+        })
+    }
+
+    /// Delegates staked HYPE to `validator`, or undelegates it when `is_undelegate` is set.
+    pub async fn token_delegate(
+        &self,
+        validator: H160,
+        wei: u64,
+        is_undelegate: bool,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.token_delegate_action(validator, wei, is_undelegate, timestamp);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `tokenDelegate` action for [`Self::sign_action`], which is signed with `nonce`.
+    pub fn token_delegate_action(
+        &self,
+        validator: H160,
+        wei: u64,
+        is_undelegate: bool,
+        nonce: u64,
+    ) -> Actions {
+        Actions::TokenDelegate(TokenDelegate {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            validator,
+            wei,
+            is_undelegate,
+            nonce,
+        })
+    }
+
+    /// Converts the signing user into a multi-sig user. Afterwards its actions have to be sent
+    /// with [`Self::multi_sig`], signed by at least `threshold` of `authorized_users`.
+    pub async fn convert_to_multi_sig_user(
+        &self,
+        authorized_users: Vec<H160>,
+        threshold: u32,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action =
+            self.convert_to_multi_sig_user_action(authorized_users, threshold, timestamp)?;
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `convertToMultiSigUser` action for [`Self::sign_action`], which is signed with
+    /// `nonce`. The authorized users are sorted as the exchange expects.
+    pub fn convert_to_multi_sig_user_action(
+        &self,
+        mut authorized_users: Vec<H160>,
+        threshold: u32,
+        nonce: u64,
+    ) -> Result<Actions> {
+        authorized_users.sort();
+        let signers = serde_json::to_string(&MultiSigSigners {
+            authorized_users,
+            threshold,
+        })
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
 
-        self.post(action, signature, timestamp).await
+        Ok(Actions::ConvertToMultiSigUser(ConvertToMultiSigUser {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            signers,
+            nonce,
+        }))
+    }
+
+    /// Sends `action` on behalf of `multi_sig_user`, with the wallet as outer signer.
+    /// `signatures` are collected with [`sign_multi_sig_action`](super::sign_multi_sig_action)
+    /// over the same action, outer signer and `nonce`.
+    pub async fn multi_sig(
+        &self,
+        multi_sig_user: H160,
+        action: Actions,
+        signatures: Vec<Signature>,
+        nonce: u64,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        if let Actions::MultiSig(_) = action {
+            return Err(Error::UnsupportedAction(
+                "multi-sig actions cannot be nested".to_string(),
+            ));
+        }
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let outer_signer = wallet.address();
+        let multi_sig = MultiSig {
+            signature_chain_id: 421614.into(),
+            signatures,
+            payload: MultiSigPayload {
+                multi_sig_user,
+                outer_signer,
+                action: Box::new(action),
+            },
+        };
+        self.send_action(Actions::MultiSig(multi_sig), nonce, wallet)
+            .await
     }
 
     pub async fn spot_transfer(
@@ -686,111 +1348,436 @@ impl ExchangeClient {
         amount: &str,
         destination: &str,
         token: &str,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         let wallet = wallet.unwrap_or(&self.wallet);
-        let hyperliquid_chain = if self.http_client.is_mainnet() {
-            "Mainnet".to_string()
-        } else {
-            "Testnet".to_string()
-        };
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.spot_transfer_action(amount, destination, token, timestamp);
+        self.send_action(action, timestamp, wallet).await
+    }
 
-        let timestamp = next_nonce();
-        let spot_send = SpotSend {
+    /// Builds a `spotSend` action for [`Self::sign_action`], which is signed with `time` as
+    /// nonce.
+    pub fn spot_transfer_action(
+        &self,
+        amount: &str,
+        destination: &str,
+        token: &str,
+        time: u64,
+    ) -> Actions {
+        Actions::SpotSend(SpotSend {
             signature_chain_id: 421614.into(),
-            hyperliquid_chain,
+            hyperliquid_chain: self.hyperliquid_chain(),
             destination: destination.to_string(),
             amount: amount.to_string(),
-            time: timestamp,
+            time,
             token: token.to_string(),
-        };
-        let signature = sign_typed_data(&spot_send, wallet)?;
-        let action = serde_json::to_value(Actions::SpotSend(spot_send))
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
+        })
+    }
+
+    /// Creates a vault led by the signing wallet, which deposits `initial_usd` (in units of
+    /// 1e-6). The response data is the new vault address.
+    pub async fn create_vault(
+        &self,
+        name: String,
+        description: String,
+        initial_usd: u64,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus<H160>> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.create_vault_action(name, description, initial_usd, timestamp);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `createVault` action for [`Self::sign_action`], which is signed with `nonce`.
+    pub fn create_vault_action(
+        &self,
+        name: String,
+        description: String,
+        initial_usd: u64,
+        nonce: u64,
+    ) -> Actions {
+        Actions::CreateVault(CreateVault {
+            name,
+            description,
+            initial_usd,
+            nonce,
+        })
+    }
+
+    /// Changes the settings of a vault led by the signing wallet.
+    pub async fn vault_modify(
+        &self,
+        vault_address: H160,
+        allow_deposits: Option<bool>,
+        always_close_on_withdraw: Option<bool>,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action =
+            self.vault_modify_action(vault_address, allow_deposits, always_close_on_withdraw);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `vaultModify` action for [`Self::sign_action`].
+    pub fn vault_modify_action(
+        &self,
+        vault_address: H160,
+        allow_deposits: Option<bool>,
+        always_close_on_withdraw: Option<bool>,
+    ) -> Actions {
+        Actions::VaultModify(VaultModify {
+            vault_address,
+            allow_deposits,
+            always_close_on_withdraw,
+        })
+    }
+
+    /// Distributes `usd` (in units of 1e-6) of a vault led by the signing wallet to its
+    /// followers.
+    pub async fn vault_distribute(
+        &self,
+        vault_address: H160,
+        usd: u64,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.vault_distribute_action(vault_address, usd);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `vaultDistribute` action for [`Self::sign_action`].
+    pub fn vault_distribute_action(&self, vault_address: H160, usd: u64) -> Actions {
+        Actions::VaultDistribute(VaultDistribute { vault_address, usd })
+    }
+
+    /// Creates a sub-account of the signing wallet. The response data is the new account address.
+    pub async fn create_sub_account(
+        &self,
+        name: String,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus<H160>> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.create_sub_account_action(name);
+        self.send_action(action, timestamp, wallet).await
+    }
 
-        self.post(action, signature, timestamp).await
+    /// Builds a `createSubAccount` action for [`Self::sign_action`].
+    pub fn create_sub_account_action(&self, name: String) -> Actions {
+        Actions::CreateSubAccount(CreateSubAccount { name })
+    }
+
+    /// Moves USDC between the master perp account and a sub-account. `usd` is in units of 1e-6.
+    pub async fn sub_account_transfer(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        usd: u64,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.sub_account_transfer_action(sub_account_user, is_deposit, usd);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `subAccountTransfer` action for [`Self::sign_action`].
+    pub fn sub_account_transfer_action(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        usd: u64,
+    ) -> Actions {
+        Actions::SubAccountTransfer(SubAccountUsdTransfer {
+            sub_account_user,
+            is_deposit,
+            usd,
+        })
+    }
+
+    /// Moves a spot token between the master account and a sub-account. `token` has the
+    /// `NAME:tokenId` form used by `spot_transfer`.
+    pub async fn sub_account_spot_transfer(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        token: &str,
+        amount: &str,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action =
+            self.sub_account_spot_transfer_action(sub_account_user, is_deposit, token, amount);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `subAccountSpotTransfer` action for [`Self::sign_action`].
+    pub fn sub_account_spot_transfer_action(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        token: &str,
+        amount: &str,
+    ) -> Actions {
+        Actions::SubAccountSpotTransfer(SubAccountSpotTransfer {
+            sub_account_user,
+            is_deposit,
+            token: token.to_string(),
+            amount: amount.to_string(),
+        })
+    }
+
+    /// Registers a new spot token. The response data is the index of the token.
+    pub async fn spot_deploy_register_token(
+        &self,
+        spec: TokenSpec,
+        max_gas: u64,
+        full_name: Option<String>,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus<serde_json::Value>> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.spot_deploy_register_token_action(spec, max_gas, full_name);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `registerToken2` spot deploy action for [`Self::sign_action`].
+    pub fn spot_deploy_register_token_action(
+        &self,
+        spec: TokenSpec,
+        max_gas: u64,
+        full_name: Option<String>,
+    ) -> Actions {
+        Actions::SpotDeploy(SpotDeploy::RegisterToken2(RegisterToken {
+            spec,
+            max_gas,
+            full_name,
+        }))
+    }
+
+    pub async fn spot_deploy_user_genesis(
+        &self,
+        token: u32,
+        user_and_wei: Vec<(H160, String)>,
+        existing_token_and_wei: Vec<(u32, String)>,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action =
+            self.spot_deploy_user_genesis_action(token, user_and_wei, existing_token_and_wei);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `userGenesis` spot deploy action for [`Self::sign_action`].
+    pub fn spot_deploy_user_genesis_action(
+        &self,
+        token: u32,
+        user_and_wei: Vec<(H160, String)>,
+        existing_token_and_wei: Vec<(u32, String)>,
+    ) -> Actions {
+        Actions::SpotDeploy(SpotDeploy::UserGenesis(UserGenesis {
+            token,
+            user_and_wei,
+            existing_token_and_wei,
+        }))
+    }
+
+    pub async fn spot_deploy_genesis(
+        &self,
+        token: u32,
+        max_supply: String,
+        no_hyperliquidity: bool,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.spot_deploy_genesis_action(token, max_supply, no_hyperliquidity);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `genesis` spot deploy action for [`Self::sign_action`].
+    pub fn spot_deploy_genesis_action(
+        &self,
+        token: u32,
+        max_supply: String,
+        no_hyperliquidity: bool,
+    ) -> Actions {
+        Actions::SpotDeploy(SpotDeploy::Genesis(Genesis {
+            token,
+            max_supply,
+            no_hyperliquidity,
+        }))
+    }
+
+    /// Registers the spot pair of two tokens. The response data is the index of the pair.
+    pub async fn spot_deploy_register_spot(
+        &self,
+        base_token: u32,
+        quote_token: u32,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus<serde_json::Value>> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.spot_deploy_register_spot_action(base_token, quote_token);
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `registerSpot` spot deploy action for [`Self::sign_action`].
+    pub fn spot_deploy_register_spot_action(&self, base_token: u32, quote_token: u32) -> Actions {
+        Actions::SpotDeploy(SpotDeploy::RegisterSpot(RegisterSpot {
+            tokens: [base_token, quote_token],
+        }))
+    }
+
+    /// Sets up the Hyperliquidity market making strategy of a spot pair.
+    pub async fn spot_deploy_register_hyperliquidity(
+        &self,
+        spot: u32,
+        start_px: Decimal,
+        order_sz: Decimal,
+        n_orders: u32,
+        n_seeded_levels: Option<u32>,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.spot_deploy_register_hyperliquidity_action(
+            spot,
+            start_px,
+            order_sz,
+            n_orders,
+            n_seeded_levels,
+        );
+        self.send_action(action, timestamp, wallet).await
+    }
+
+    /// Builds a `registerHyperliquidity` spot deploy action for [`Self::sign_action`].
+    pub fn spot_deploy_register_hyperliquidity_action(
+        &self,
+        spot: u32,
+        start_px: Decimal,
+        order_sz: Decimal,
+        n_orders: u32,
+        n_seeded_levels: Option<u32>,
+    ) -> Actions {
+        Actions::SpotDeploy(SpotDeploy::RegisterHyperliquidity(RegisterHyperliquidity {
+            spot,
+            start_px: start_px.to_string(),
+            order_sz: order_sz.to_string(),
+            n_orders,
+            n_seeded_levels,
+        }))
     }
 
     pub async fn set_referrer(
         &self,
         code: String,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         let wallet = wallet.unwrap_or(&self.wallet);
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.set_referrer_action(code);
+        self.send_action(action, timestamp, wallet).await
+    }
 
-        let action = Actions::SetReferrer(SetReferrer { code });
+    /// Builds a `setReferrer` action for [`Self::sign_action`].
+    pub fn set_referrer_action(&self, code: String) -> Actions {
+        Actions::SetReferrer(SetReferrer { code })
+    }
 
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+    /// Schedules a cancellation of all open orders at `time` (in milliseconds, at least 5 seconds
+    /// in the future), or removes the current schedule when `time` is `None`. See
+    /// [`DeadMansSwitch`](super::DeadMansSwitch) for keeping the schedule armed while the process is alive.
+    pub async fn schedule_cancel(
+        &self,
+        time: Option<u64>,
+        wallet: Option<&S>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(&self.wallet);
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.schedule_cancel_action(time);
+        self.send_action(action, timestamp, wallet).await
+    }
 
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
-        self.post(action, signature, timestamp).await
+    /// Builds a `scheduleCancel` action for [`Self::sign_action`].
+    pub fn schedule_cancel_action(&self, time: Option<u64>) -> Actions {
+        Actions::ScheduleCancel(ScheduleCancel { time })
     }
 
     pub async fn approve_builder_fee(
         &self,
-        builder: String,
+        builder: H160,
         max_fee_rate: String,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&S>,
     ) -> Result<ExchangeResponseStatus> {
         let wallet = wallet.unwrap_or(&self.wallet);
-        let timestamp = next_nonce();
-
-        let hyperliquid_chain = if self.http_client.is_mainnet() {
-            "Mainnet".to_string()
-        } else {
-            "Testnet".to_string()
-        };
+        let timestamp = self.next_nonce(wallet.address())?;
+        let action = self.approve_builder_fee_action(builder, max_fee_rate, timestamp);
+        self.send_action(action, timestamp, wallet).await
+    }
 
-        let action = Actions::ApproveBuilderFee(ApproveBuilderFee {
+    /// Builds an `approveBuilderFee` action for [`Self::sign_action`], which is signed with
+    /// `nonce`.
+    pub fn approve_builder_fee_action(
+        &self,
+        builder: H160,
+        max_fee_rate: String,
+        nonce: u64,
+    ) -> Actions {
+        Actions::ApproveBuilderFee(ApproveBuilderFee {
             signature_chain_id: 421614.into(),
-            hyperliquid_chain,
+            hyperliquid_chain: self.hyperliquid_chain(),
             builder,
             max_fee_rate,
-            nonce: timestamp,
-        });
-
-        let connection_id = action.hash(timestamp, self.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
-
-        let is_mainnet = self.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
-        self.post(action, signature, timestamp).await
+            nonce,
+        })
     }
 }
 
-fn round_to_decimals(value: f64, decimals: u32) -> f64 {
-    let factor = 10f64.powi(decimals as i32);
-    (value * factor).round() / factor
-}
-
-fn round_to_significant_and_decimal(value: f64, sig_figs: u32, max_decimals: u32) -> f64 {
-    let abs_value = value.abs();
-    let magnitude = abs_value.log10().floor() as i32;
-    let scale = 10f64.powi(sig_figs as i32 - magnitude - 1);
-    let rounded = (abs_value * scale).round() / scale;
-    round_to_decimals(rounded.copysign(value), max_decimals)
-}
-
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use std::str::FromStr;
 
     use super::*;
+    use crate::signature::sign_l1_action;
     use crate::{
         exchange::order::{Limit, OrderRequest, Trigger},
-        Order,
+        Order, TpSl,
     };
 
-    fn get_wallet() -> Result<LocalWallet> {
+    pub(crate) fn get_wallet() -> Result<LocalWallet> {
         let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
         priv_key
             .parse::<LocalWallet>()
             .map_err(|e| Error::Wallet(e.to_string()))
     }
 
+    /// Testnet client signing with the test key, built from the perp universe in `meta_json`
+    /// without any request.
+    pub(crate) fn offline_client(
+        meta_json: &str,
+        vault_address: Option<H160>,
+    ) -> Result<ExchangeClient> {
+        let meta: Meta =
+            serde_json::from_str(meta_json).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let spot_meta: SpotMeta = serde_json::from_str(r#"{"universe": [], "tokens": []}"#)
+            .map_err(|e| Error::JsonParse(e.to_string()))?;
+        Ok(ExchangeClient::from_meta(
+            None,
+            get_wallet()?,
+            Some(BaseUrl::Testnet),
+            meta,
+            spot_meta,
+            vault_address,
+        ))
+    }
+
     #[test]
     fn test_limit_order_action_hashing() -> Result<()> {
         let wallet = get_wallet()?;
@@ -801,12 +1788,10 @@ mod tests {
                 limit_px: "2000.0".to_string(),
                 sz: "3.5".to_string(),
                 reduce_only: false,
-                order_type: Order::Limit(Limit {
-                    tif: "Ioc".to_string(),
-                }),
+                order_type: Order::Limit(Limit { tif: Tif::Ioc }),
                 cloid: None,
             }],
-            grouping: "na".to_string(),
+            grouping: Grouping::Na,
             builder: None,
         });
         let connection_id = action.hash(1583838, None)?;
@@ -832,12 +1817,10 @@ mod tests {
                 limit_px: "2000.0".to_string(),
                 sz: "3.5".to_string(),
                 reduce_only: false,
-                order_type: Order::Limit(Limit {
-                    tif: "Ioc".to_string(),
-                }),
+                order_type: Order::Limit(Limit { tif: Tif::Ioc }),
                 cloid: Some(uuid_to_hex_string(cloid.unwrap())),
             }],
-            grouping: "na".to_string(),
+            grouping: Grouping::Na,
             builder: None,
         });
         let connection_id = action.hash(1583838, None)?;
@@ -855,12 +1838,12 @@ mod tests {
     fn test_tpsl_order_action_hashing() -> Result<()> {
         for (tpsl, mainnet_signature, testnet_signature) in [
             (
-                "tp",
+                TpSl::Tp,
                 "b91e5011dff15e4b4a40753730bda44972132e7b75641f3cac58b66159534a170d422ee1ac3c7a7a2e11e298108a2d6b8da8612caceaeeb3e571de3b2dfda9e41b",
                 "6df38b609904d0d4439884756b8f366f22b3a081801dbdd23f279094a2299fac6424cb0cdc48c3706aeaa368f81959e91059205403d3afd23a55983f710aee871b"
             ),
             (
-                "sl",
+                TpSl::Sl,
                 "8456d2ace666fce1bee1084b00e9620fb20e810368841e9d4dd80eb29014611a0843416e51b1529c22dd2fc28f7ff8f6443875635c72011f60b62cbb8ce90e2d1c",
                 "eb5bdb52297c1d19da45458758bd569dcb24c07e5c7bd52cf76600fd92fdd8213e661e21899c985421ec018a9ee7f3790e7b7d723a9932b7b5adcd7def5354601c"
             )
@@ -877,14 +1860,69 @@ mod tests {
                         order_type: Order::Trigger(Trigger {
                             trigger_px: "2000.0".to_string(),
                             is_market: true,
-                            tpsl: tpsl.to_string(),
+                            tpsl,
                         }),
                         cloid: None,
                     }
                 ],
-                grouping: "na".to_string(),
+                grouping: Grouping::Na,
+                builder: None,
+            });
+            let connection_id = action.hash(1583838, None)?;
+
+            let signature = sign_l1_action(&wallet, connection_id, true)?;
+            assert_eq!(signature.to_string(), mainnet_signature);
+
+            let signature = sign_l1_action(&wallet, connection_id, false)?;
+            assert_eq!(signature.to_string(), testnet_signature);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_grouped_tpsl_order_action_hashing() -> Result<()> {
+        let coin_to_asset = HashMap::from([("ETH".to_string(), 1)]);
+        for (grouping, grouping_str, mainnet_signature, testnet_signature) in [
+            (
+                Grouping::NormalTpsl,
+                "normalTpsl",
+                "12cabfacfb32aa82e5c387a27054996311d4ba72a92aae0f842425e10e43626a1578d6d3b8912c2f563bee41611d7fd28981d76f7189eae3008e368f1fb745371b",
+                "e397114a71983f33e0e8a895833b92b54dc52411d4f2e2b5a057368a76e8f2875235703ec5e951530014808cd92bc5eae7f70f01f95a1d9999ce4df9ad8ccc401b"
+            ),
+            (
+                Grouping::PositionTpsl,
+                "positionTpsl",
+                "84fedcd4436c3160de5005ca318809e7d471da34db6121a5ea935ff181d898987dd5620a041a84a9fbc5f18dcb7e9bae425d869252ef6aa8d7b0249744b7b8d01b",
+                "3ac68a86a5615acf2a2d9b0c928e73caefbc49344669a96249197f06826c20c45a35abe2799f3897076ef46cb108d933f3b5559cc85f50b03ae031655fb5f4371c"
+            ),
+        ] {
+            let wallet = get_wallet()?;
+            let entry = ClientOrderRequest {
+                asset: "ETH".to_string(),
+                is_buy: true,
+                reduce_only: false,
+                limit_px: "2000.0".parse()?,
+                sz: "3.5".parse()?,
+                cloid: None,
+                order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Gtc }),
+            };
+            let orders = entry
+                .with_tpsl(Some("2100".parse()?), Some("1900".parse()?))
+                .into_iter()
+                .map(|order| order.convert(&coin_to_asset))
+                .collect::<Result<Vec<_>>>()?;
+            let action = Actions::Order(BulkOrder {
+                orders,
+                grouping,
                 builder: None,
             });
+
+            let value =
+                serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+            assert_eq!(value["grouping"], grouping_str);
+            assert_eq!(value["orders"][1]["t"]["trigger"]["tpsl"], "tp");
+            assert_eq!(value["orders"][2]["b"], false);
+
             let connection_id = action.hash(1583838, None)?;
 
             let signature = sign_l1_action(&wallet, connection_id, true)?;
@@ -896,6 +1934,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_position_tpsl_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let coin_to_asset = HashMap::from([("ETH".to_string(), 1)]);
+        // closing a short takes profit below and stops out above the market
+        let orders = ClientOrderRequest::position_tpsl(
+            "ETH",
+            "-3.5".parse()?,
+            Some("1900".parse()?),
+            Some("2100".parse()?),
+        )
+        .into_iter()
+        .map(|order| order.convert(&coin_to_asset))
+        .collect::<Result<Vec<_>>>()?;
+        let action = Actions::Order(BulkOrder {
+            orders,
+            grouping: Grouping::PositionTpsl,
+            builder: None,
+        });
+
+        let value = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert_eq!(value["grouping"], "positionTpsl");
+        assert_eq!(value["orders"][0]["b"], true);
+        assert_eq!(value["orders"][0]["r"], true);
+        assert_eq!(value["orders"][0]["s"], "3.5");
+        assert_eq!(value["orders"][1]["t"]["trigger"]["tpsl"], "sl");
+
+        let connection_id = action.hash(1583838, None)?;
+        let signature = sign_l1_action(&wallet, connection_id, false)?;
+        assert_eq!(signature.to_string(), "efbe9498ba108e53c811ad2d2833e0bd5d40c4b49ee79fe77115410db10f4d756fe4a499d2f6721ddab01bcfe774f47cf89cad389430323761b124e19b61a9901b");
+        Ok(())
+    }
+
     #[test]
     fn test_cancel_action_hashing() -> Result<()> {
         let wallet = get_wallet()?;
@@ -915,4 +1986,449 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_twap_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let action = Actions::TwapOrder(TwapOrder {
+            twap: TwapRequest {
+                asset: 1,
+                is_buy: true,
+                sz: "3.5".to_string(),
+                reduce_only: false,
+                minutes: 30,
+                randomize: true,
+            },
+        });
+        assert_eq!(
+            serde_json::to_string(&action).map_err(|e| Error::JsonParse(e.to_string()))?,
+            r#"{"type":"twapOrder","twap":{"a":1,"b":true,"s":"3.5","r":false,"m":30,"t":true}}"#
+        );
+        let connection_id = action.hash(1583838, None)?;
+
+        let signature = sign_l1_action(&wallet, connection_id, true)?;
+        assert_eq!(signature.to_string(), "110eee2bedf0cc59d99ceea1aace3b605b9b14a499daa7f3ca73e33e6da185f873670abeb047ffe9990d5334b855cba68c47855eb6cdde52579caf934d01322b1b");
+
+        let signature = sign_l1_action(&wallet, connection_id, false)?;
+        assert_eq!(signature.to_string(), "3d503305ba84f7d47805c8508bac6dbb53e053ddaec6206b14cf1aad7eba1ed76dd11ec1cbdd21ead38405cbe5e755347e894a70dba83b0fcc9c8c1987f715321c");
+
+        let action = Actions::TwapCancel(TwapCancel {
+            asset: 1,
+            twap_id: 1234,
+        });
+        assert_eq!(
+            serde_json::to_string(&action).map_err(|e| Error::JsonParse(e.to_string()))?,
+            r#"{"type":"twapCancel","a":1,"t":1234}"#
+        );
+        let connection_id = action.hash(1583838, None)?;
+
+        let signature = sign_l1_action(&wallet, connection_id, true)?;
+        assert_eq!(signature.to_string(), "4b9424963de0a268e6214c528afbcf699faccd305117c94eecae47f75305e4a16cb926843414d98aa66cf9b078378302260434368fe67436f1ceac751a70fe421c");
+
+        let signature = sign_l1_action(&wallet, connection_id, false)?;
+        assert_eq!(signature.to_string(), "ce13e08b1e7a21a6f12dabefa4e7639ec6e40ec4a3acc6b2ee339e77b245443259e79efbb59d097b93e448566fa6b08da4a8730672890176a55b02d4013681401c");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_schedule_cancel_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let action = Actions::ScheduleCancel(ScheduleCancel { time: None });
+        assert_eq!(
+            serde_json::to_string(&action).map_err(|e| Error::JsonParse(e.to_string()))?,
+            r#"{"type":"scheduleCancel"}"#
+        );
+
+        let action = Actions::ScheduleCancel(ScheduleCancel {
+            time: Some(1583848),
+        });
+        let connection_id = action.hash(1583838, None)?;
+
+        let signature = sign_l1_action(&wallet, connection_id, true)?;
+        assert_eq!(signature.to_string(), "b41fe7bf990a0b17485cb1643d1444e1cf8286cd9e53cb31c4e04fe0dd595480388908c0112de78e5fe22ca7abcca89b9629390fa1ba7d29f158fca60ca9c3361b");
+
+        let signature = sign_l1_action(&wallet, connection_id, false)?;
+        assert_eq!(signature.to_string(), "e07028dd763c1949c6b52bc45c86edf1c331c52f33ed573923dc5e5593ce62ce0a472ba08deeef53300fa1b3917bb37cc9306fbefeafd246974261d42e88443e1c");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sub_account_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let sub_account_user: H160 = "0x1d9470d4b963f552e6f671a81619d395877bf409"
+            .parse()
+            .unwrap();
+
+        let action = Actions::CreateSubAccount(CreateSubAccount {
+            name: "example".to_string(),
+        });
+        let connection_id = action.hash(1583838, None)?;
+        assert_eq!(
+            sign_l1_action(&wallet, connection_id, true)?.to_string(),
+            "641ae838866f45167ac83ff50922925254bcd6e7df305cd9237a85b546a37d6a53bd2e567c6fdce4417272162303511731b77f0bf1db11d5b594133d969153d91b"
+        );
+
+        let action = Actions::SubAccountTransfer(SubAccountUsdTransfer {
+            sub_account_user,
+            is_deposit: true,
+            usd: 1_000_000,
+        });
+        assert_eq!(
+            serde_json::to_string(&action).map_err(|e| Error::JsonParse(e.to_string()))?,
+            r#"{"type":"subAccountTransfer","subAccountUser":"0x1d9470d4b963f552e6f671a81619d395877bf409","isDeposit":true,"usd":1000000}"#
+        );
+        let connection_id = action.hash(1583838, None)?;
+        assert_eq!(
+            sign_l1_action(&wallet, connection_id, true)?.to_string(),
+            "d766ff2eb59b1ab6d33c474fa22830e302c5c116aadb9b7c3cdc08ea3e4f08ba0ba874106de56d86501e1eaefce781413880775c3a632940149788e688ba00201c"
+        );
+
+        let action = Actions::SubAccountSpotTransfer(SubAccountSpotTransfer {
+            sub_account_user,
+            is_deposit: false,
+            token: "PURR:0xc4bf3f870c0e9465323c0b6ed28096c2".to_string(),
+            amount: "100".to_string(),
+        });
+        let connection_id = action.hash(1583838, None)?;
+        assert_eq!(
+            sign_l1_action(&wallet, connection_id, true)?.to_string(),
+            "2e56e6293bda57b7eb07ddeb8ba530475b9ffa3caff0e5bc2a59c5ca41b9925516c5ea0d1f25687bdbfb06a56ec989b441fa4ccdf3fe37dbc1ab0bc63dbeefa71b"
+        );
+
+        // orders of a sub-account view are hashed with the sub-account as vault address
+        let action = Actions::Cancel(BulkCancel {
+            cancels: vec![CancelRequest {
+                asset: 1,
+                oid: 82382,
+            }],
+        });
+        assert_ne!(
+            action.hash(1583838, Some(sub_account_user))?,
+            action.hash(1583838, None)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vault_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let vault_address: H160 = "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303"
+            .parse()
+            .unwrap();
+
+        let action = Actions::CreateVault(CreateVault {
+            name: "Test Vault".to_string(),
+            description: "A vault for testing".to_string(),
+            initial_usd: 100_000_000,
+            nonce: 1583838,
+        });
+        assert_eq!(
+            serde_json::to_string(&action).map_err(|e| Error::JsonParse(e.to_string()))?,
+            r#"{"type":"createVault","name":"Test Vault","description":"A vault for testing","initialUsd":100000000,"nonce":1583838}"#
+        );
+        let connection_id = action.hash(1583838, None)?;
+        assert_eq!(
+            sign_l1_action(&wallet, connection_id, true)?.to_string(),
+            "a6fbf2903dac809229f57f4755bf3e8c7923a53ae83132539d52ae79ca4aa5746a36d7ca7a5dc600da923eda5c96a82f505534f5020c803ce2486275888615351c"
+        );
+
+        let action = Actions::VaultModify(VaultModify {
+            vault_address,
+            allow_deposits: Some(false),
+            always_close_on_withdraw: None,
+        });
+        assert_eq!(
+            serde_json::to_string(&action).map_err(|e| Error::JsonParse(e.to_string()))?,
+            r#"{"type":"vaultModify","vaultAddress":"0xdfc24b077bc1425ad1dea75bcb6f8158e10df303","allowDeposits":false,"alwaysCloseOnWithdraw":null}"#
+        );
+        let connection_id = action.hash(1583838, None)?;
+        assert_eq!(
+            sign_l1_action(&wallet, connection_id, true)?.to_string(),
+            "3dfaf15442fa134be41a2eb107d0b7ee7d3295f7d692418452c54491e3dc7c87725d25c9c0549d692858ae3a8afa6ca4b2dec62d50ad3db9a638e8b4d2a96afd1c"
+        );
+
+        let action = Actions::VaultDistribute(VaultDistribute {
+            vault_address,
+            usd: 5_000_000,
+        });
+        let connection_id = action.hash(1583838, None)?;
+        assert_eq!(
+            sign_l1_action(&wallet, connection_id, true)?.to_string(),
+            "3882b6c76072b4baaaf745a44af233d03ea97aa9b28a2992d5c6ce6494d1ee2700ff90d50c8c3dbecbfa61c0bcd97c197460fb03cd6cbbdbb81ca5679c82b88c1b"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spot_deploy_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let user: H160 = "0x0d1d9635d0640821d15e323ac8adadfa9c111414"
+            .parse()
+            .unwrap();
+        for (spot_deploy, expected_json, expected_signature) in [
+            (
+                SpotDeploy::RegisterToken2(RegisterToken {
+                    spec: TokenSpec {
+                        name: "TEST0".to_string(),
+                        sz_decimals: 2,
+                        wei_decimals: 8,
+                    },
+                    max_gas: 1_000_000_000_000,
+                    full_name: None,
+                }),
+                r#"{"type":"spotDeploy","registerToken2":{"spec":{"name":"TEST0","szDecimals":2,"weiDecimals":8},"maxGas":1000000000000}}"#,
+                "d1c9ecdfaae5dc289fef479dc2c8d16f4ec57da23843753e730f169ab42613a771b2b90a61a8c18bb92bfb30bf505d4f63bcfec23c495072d6993041193716fb1c",
+            ),
+            (
+                SpotDeploy::UserGenesis(UserGenesis {
+                    token: 1,
+                    user_and_wei: vec![(user, "100000000000000".to_string())],
+                    existing_token_and_wei: vec![(0, "100000".to_string())],
+                }),
+                r#"{"type":"spotDeploy","userGenesis":{"token":1,"userAndWei":[["0x0d1d9635d0640821d15e323ac8adadfa9c111414","100000000000000"]],"existingTokenAndWei":[[0,"100000"]]}}"#,
+                "ab335bfecc4b2c08b53ee57486678c8f47ae616c48c04669b0eaa9585401532150b68056aeaec42af0a3656cfb00a1eb83bbf6b734b49ba0a5593a55e2c011c41c",
+            ),
+            (
+                SpotDeploy::Genesis(Genesis {
+                    token: 1,
+                    max_supply: "100000000000000".to_string(),
+                    no_hyperliquidity: false,
+                }),
+                r#"{"type":"spotDeploy","genesis":{"token":1,"maxSupply":"100000000000000"}}"#,
+                "511c4219818d68d208a90f435dd82b733cd1ba558d9cdeaf8d029341493986e8495c0042493a5a3a7ae8a922ab3559f912e684217f98552ef25ace1b066476111c",
+            ),
+            (
+                SpotDeploy::RegisterSpot(RegisterSpot { tokens: [1, 0] }),
+                r#"{"type":"spotDeploy","registerSpot":{"tokens":[1,0]}}"#,
+                "a516bd1b002f319ca55477caab5537b7358d7da46016625e204691c00df659553d81a41fa8d580f4b94042b9d7f23e96ab8caa2cfc8cec8860a0cf47854153611c",
+            ),
+            (
+                SpotDeploy::RegisterHyperliquidity(RegisterHyperliquidity {
+                    spot: 2,
+                    start_px: "2".to_string(),
+                    order_sz: "1000".to_string(),
+                    n_orders: 100,
+                    n_seeded_levels: Some(10),
+                }),
+                r#"{"type":"spotDeploy","registerHyperliquidity":{"spot":2,"startPx":"2","orderSz":"1000","nOrders":100,"nSeededLevels":10}}"#,
+                "f2614f7285a3d7d770d858eabdd0035511252309f350d390aec695d4c455b05c42c2d62f960e14f26f735ddd91987ff92fddcb77284d010b9eeeaecfb79dfdd41c",
+            ),
+        ] {
+            let action = Actions::SpotDeploy(spot_deploy);
+            assert_eq!(
+                serde_json::to_string(&action).map_err(|e| Error::JsonParse(e.to_string()))?,
+                expected_json
+            );
+
+            let connection_id = action.hash(1583838, None)?;
+            let signature = sign_l1_action(&wallet, connection_id, true)?;
+            assert_eq!(signature.to_string(), expected_signature);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_offline_signing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let client = offline_client(
+            r#"{"universe": [{"name": "BTC", "szDecimals": 5}, {"name": "ETH", "szDecimals": 4}]}"#,
+            None,
+        )?;
+
+        let order = ClientOrderRequest {
+            asset: "ETH".to_string(),
+            is_buy: true,
+            reduce_only: false,
+            limit_px: "2000".parse()?,
+            sz: "3.5".parse()?,
+            cloid: None,
+            order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Ioc }),
+        };
+        let modify = client.modify_action(vec![ClientModifyRequest {
+            oid: 82382,
+            order: ClientOrderRequest {
+                asset: "ETH".to_string(),
+                is_buy: true,
+                reduce_only: false,
+                limit_px: "2100".parse()?,
+                sz: "3.5".parse()?,
+                cloid: None,
+                order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Gtc }),
+            },
+        }])?;
+        assert!(matches!(&modify, Actions::BatchModify(m) if m.modifies[0].order.asset == 1));
+        let cancel = client.cancel_by_cloid_action(vec![ClientCancelRequestCloid {
+            asset: "BTC".to_string(),
+            cloid: uuid::Uuid::nil(),
+        }])?;
+        assert!(matches!(&cancel, Actions::CancelByCloid(c) if c.cancels[0].asset == 0));
+        let leverage = client.update_leverage_action(5, "ETH", true)?;
+        assert!(matches!(leverage, Actions::UpdateLeverage(l) if l.asset == 1 && l.leverage == 5));
+        assert!(matches!(
+            client.twap_cancel_action("SOL", 1),
+            Err(Error::AssetNotFound)
+        ));
+        let convert = client.convert_to_multi_sig_user_action(
+            vec![H160::from_low_u64_be(2), H160::from_low_u64_be(1)],
+            1,
+            1583838,
+        )?;
+        assert!(
+            matches!(&convert, Actions::ConvertToMultiSigUser(c) if c.signers.starts_with(
+                r#"{"authorizedUsers":["0x0000000000000000000000000000000000000001","#
+            ))
+        );
+
+        // an agent approval can be signed without the agent key
+        let approve_agent =
+            client.approve_agent_action(H160::from_low_u64_be(7), Some("bot"), 1583838);
+        let payload = client
+            .sign_action(approve_agent, 1583838, None, None)
+            .await?;
+        assert_eq!(payload.action["agentName"], "bot");
+        assert_eq!(payload.recover_signer(false)?, wallet.address());
+
+        let action = client.order_action(vec![order], Grouping::Na, None)?;
+        let connection_id = action.hash(1583838, None)?;
+        let payload = client.sign_action(action, 1583838, None, None).await?;
+
+        let json = serde_json::to_string(&payload).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let payload: ExchangePayload =
+            serde_json::from_str(&json).map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert_eq!(payload.nonce, 1583838);
+        assert_eq!(payload.action["orders"][0]["a"], 1);
+        assert_eq!(
+            payload.signature,
+            sign_l1_action(&wallet, connection_id, false)?
+        );
+
+        // user-signed actions are signed with their own nonce
+        let usd_send = client.usdc_transfer_action(
+            "1",
+            "0x0D1d9635D0640821d15e323ac8AdADfA9c111414",
+            1690393044548,
+        );
+        assert!(matches!(
+            client
+                .sign_action(usd_send.clone(), 1583838, None, None)
+                .await,
+            Err(Error::Nonce(_))
+        ));
+        let payload = client
+            .sign_action(usd_send, 1690393044548, None, None)
+            .await?;
+        assert_eq!(payload.nonce, 1690393044548);
+        assert_eq!(payload.signature.to_string(), "214d507bbdaebba52fa60928f904a8b2df73673e3baba6133d66fe846c7ef70451e82453a6d8db124e7ed6e60fa00d4b7c46e4d96cb2bd61fd81b6e8953cc9d21b");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recover_payload_signer() -> Result<()> {
+        let wallet = get_wallet()?;
+        let vault_address = H160::from_str("0x1719884eb866cb12b2287399b15f7db5e7d775ea")
+            .map_err(|e| Error::GenericParse(e.to_string()))?;
+        let client = offline_client(
+            r#"{"universe": [{"name": "ETH", "szDecimals": 4}]}"#,
+            Some(vault_address),
+        )?;
+
+        let cancel = client.cancel_action(vec![ClientCancelRequest {
+            asset: "ETH".to_string(),
+            oid: 82382,
+        }])?;
+        let mut payload = client.sign_action(cancel, 1583838, None, None).await?;
+        assert_eq!(payload.recover_signer(false)?, wallet.address());
+        assert_ne!(payload.recover_signer(true)?, wallet.address());
+        payload.nonce += 1;
+        assert_ne!(payload.recover_signer(false)?, wallet.address());
+
+        let withdraw = Withdraw3 {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            destination: "0x0D1d9635D0640821d15e323ac8AdADfA9c111414".to_string(),
+            amount: "1".to_string(),
+            time: 1690393044548,
+        };
+        let payload = client
+            .sign_action(Actions::Withdraw3(withdraw), 1690393044548, None, None)
+            .await?;
+        assert_eq!(payload.recover_signer(false)?, wallet.address());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_expires_after_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let action = Actions::Cancel(BulkCancel {
+            cancels: vec![CancelRequest {
+                asset: 1,
+                oid: 82382,
+            }],
+        });
+        let expires_after: u64 = 1583838 + 5000;
+
+        let mut bytes = action.to_msgpack()?;
+        bytes.extend(1583838u64.to_be_bytes());
+        bytes.extend([0, 0]);
+        bytes.extend(expires_after.to_be_bytes());
+        let connection_id = action.hash_with_expiry(1583838, None, Some(expires_after))?;
+        assert_eq!(connection_id, H256(ethers::utils::keccak256(bytes)));
+        assert_ne!(connection_id, action.hash(1583838, None)?);
+        assert_eq!(
+            action.hash_with_expiry(1583838, None, None)?,
+            action.hash(1583838, None)?
+        );
+
+        let signature = sign_l1_action(&wallet, connection_id, false)?;
+        assert_eq!(signature.to_string(), "f7ca3d2fc881a88103d34376bc3cccba3b3276f71b5c627bcabb64880237dc8661a78096d35d527b08525143cdcb5943bf68fbedaa5ba262d44b3218fe7c029d1c");
+
+        let client = offline_client(r#"{"universe": []}"#, None)?;
+        let payload = client
+            .sign_action(action.clone(), 1583838, Some(expires_after), None)
+            .await?;
+        assert_eq!(payload.signature, signature);
+        assert_eq!(payload.recover_signer(false)?, wallet.address());
+        let json = serde_json::to_value(&payload).map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert_eq!(json["expiresAfter"], expires_after);
+
+        // the expiry of the client applies to every action
+        let payload = client
+            .expiring_after(expires_after)
+            .sign_action(action, 1583838, None, None)
+            .await?;
+        assert_eq!(payload.signature, signature);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_post_keeps_request_errors() -> Result<()> {
+        // nothing listens once the listener is dropped
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")
+            .map_err(|e| Error::GenericRequest(e.to_string()))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| Error::GenericRequest(e.to_string()))?;
+        drop(listener);
+
+        let mut client = offline_client(r#"{"universe": []}"#, None)?
+            .with_rate_limiter(Arc::new(RateLimiter::new(3)));
+        client.http_client.base_url = BaseUrl::Custom {
+            http: format!("http://{addr}"),
+            ws: format!("ws://{addr}/ws"),
+            is_mainnet: false,
+        };
+        let action = Actions::ScheduleCancel(ScheduleCancel { time: None });
+        let payload = client.sign_action(action, 1583838, None, None).await?;
+
+        let output = client.post_signed::<ExchangeResponseStatus>(&payload).await;
+        assert!(matches!(output, Err(Error::Connect(_))));
+        // the request and its two retries spent the whole budget
+        let output = client.post_signed::<ExchangeResponseStatus>(&payload).await;
+        assert!(matches!(output, Err(Error::RateLimited { weight: 1, .. })));
+        Ok(())
+    }
 }
@@ -1,3 +1,4 @@
+use crate::Decimal;
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug, Clone)]
@@ -8,11 +9,17 @@ pub struct RestingOrder {
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FilledOrder {
-    pub total_sz: String,
-    pub avg_px: String,
+    pub total_sz: Decimal,
+    pub avg_px: Decimal,
     pub oid: u64,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningTwap {
+    pub twap_id: u64,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum ExchangeDataStatus {
@@ -22,24 +29,216 @@ pub enum ExchangeDataStatus {
     Error(String),
     Resting(RestingOrder),
     Filled(FilledOrder),
+    Running(RunningTwap),
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct ExchangeDataStatuses {
+    #[serde(default)]
     pub statuses: Vec<ExchangeDataStatus>,
+    /// Set instead of `statuses` by single-status actions such as `twapOrder` and `twapCancel`.
+    pub status: Option<ExchangeDataStatus>,
 }
 
+/// `T` is the type of `data`, which is the per-order statuses for most actions.
 #[derive(Deserialize, Debug, Clone)]
-pub struct ExchangeResponse {
+pub struct ExchangeResponse<T = ExchangeDataStatuses> {
     #[serde(rename = "type")]
     pub response_type: String,
-    pub data: Option<ExchangeDataStatuses>,
+    pub data: Option<T>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "status", content = "response")]
-pub enum ExchangeResponseStatus {
-    Ok(ExchangeResponse),
+pub enum ExchangeResponseStatus<T = ExchangeDataStatuses> {
+    Ok(ExchangeResponse<T>),
     Err(String),
 }
+
+/// Known reasons for Hyperliquid to reject an order, cancel or modify. Messages that are not
+/// recognised are kept verbatim in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderRejectReason {
+    BelowMinNotional,
+    InsufficientMargin,
+    InsufficientSpotBalance,
+    PostOnlyWouldCross,
+    ReduceOnlyIncreasesPosition,
+    IocNoMatch,
+    InvalidTickSize,
+    InvalidPrice,
+    InvalidSize,
+    PriceTooFarFromReference,
+    OpenInterestCap,
+    RateLimited,
+    OrderNotFound,
+    CannotModify,
+    UnknownUserOrWallet,
+    Other(String),
+}
+
+impl OrderRejectReason {
+    const PATTERNS: &'static [(&'static str, OrderRejectReason)] = &[
+        ("minimum value", OrderRejectReason::BelowMinNotional),
+        ("insufficient margin", OrderRejectReason::InsufficientMargin),
+        (
+            "insufficient spot balance",
+            OrderRejectReason::InsufficientSpotBalance,
+        ),
+        ("post only", OrderRejectReason::PostOnlyWouldCross),
+        (
+            "reduce only order would increase",
+            OrderRejectReason::ReduceOnlyIncreasesPosition,
+        ),
+        ("could not immediately match", OrderRejectReason::IocNoMatch),
+        ("tick size", OrderRejectReason::InvalidTickSize),
+        ("invalid price", OrderRejectReason::InvalidPrice),
+        ("invalid size", OrderRejectReason::InvalidSize),
+        (
+            "away from the reference price",
+            OrderRejectReason::PriceTooFarFromReference,
+        ),
+        ("open interest", OrderRejectReason::OpenInterestCap),
+        (
+            "too many cumulative requests",
+            OrderRejectReason::RateLimited,
+        ),
+        (
+            "never placed, already canceled, or filled",
+            OrderRejectReason::OrderNotFound,
+        ),
+        ("cannot modify", OrderRejectReason::CannotModify),
+        ("user or api wallet", OrderRejectReason::UnknownUserOrWallet),
+    ];
+
+    pub fn from_message(message: &str) -> OrderRejectReason {
+        let lowercase = message.to_lowercase();
+        Self::PATTERNS
+            .iter()
+            .find(|(pattern, _)| lowercase.contains(pattern))
+            .map(|(_, reason)| reason.clone())
+            .unwrap_or_else(|| OrderRejectReason::Other(message.to_string()))
+    }
+}
+
+impl ExchangeDataStatus {
+    /// Classified rejection for `Error` statuses, `None` otherwise.
+    pub fn reject_reason(&self) -> Option<OrderRejectReason> {
+        match self {
+            ExchangeDataStatus::Error(message) => Some(OrderRejectReason::from_message(message)),
+            _ => None,
+        }
+    }
+}
+
+impl<T> ExchangeResponseStatus<T> {
+    /// Classified rejection of the whole request for `Err` responses, `None` otherwise.
+    pub fn reject_reason(&self) -> Option<OrderRejectReason> {
+        match self {
+            ExchangeResponseStatus::Err(message) => Some(OrderRejectReason::from_message(message)),
+            ExchangeResponseStatus::Ok(_) => None,
+        }
+    }
+}
+
+impl ExchangeResponseStatus {
+    /// Classified rejection of every order in a bulk request, in request order. `None` entries
+    /// are orders that were accepted.
+    pub fn order_reject_reasons(&self) -> Vec<Option<OrderRejectReason>> {
+        match self {
+            ExchangeResponseStatus::Ok(ExchangeResponse {
+                data: Some(data), ..
+            }) => data
+                .statuses
+                .iter()
+                .chain(&data.status)
+                .map(ExchangeDataStatus::reject_reason)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reject_reason_classification() {
+        let response: ExchangeResponseStatus = serde_json::from_str(
+            r#"{"status": "ok", "response": {"type": "order", "data": {"statuses": [
+                {"resting": {"oid": 77738308}},
+                {"error": "Order must have minimum value of $10."},
+                {"error": "Insufficient margin to place order. asset=0"},
+                {"error": "Post only order would have immediately matched, bbo was 1800.1. asset=1"},
+                {"error": "Reduce only order would increase position. asset=1"},
+                {"error": "Order could not immediately match against any resting orders. asset=1"},
+                {"error": "Price must be divisible by tick size. asset=0"},
+                {"error": "Something new"}
+            ]}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.order_reject_reasons(),
+            vec![
+                None,
+                Some(OrderRejectReason::BelowMinNotional),
+                Some(OrderRejectReason::InsufficientMargin),
+                Some(OrderRejectReason::PostOnlyWouldCross),
+                Some(OrderRejectReason::ReduceOnlyIncreasesPosition),
+                Some(OrderRejectReason::IocNoMatch),
+                Some(OrderRejectReason::InvalidTickSize),
+                Some(OrderRejectReason::Other("Something new".to_string())),
+            ]
+        );
+        assert_eq!(response.reject_reason(), None);
+
+        let response: ExchangeResponseStatus = serde_json::from_str(
+            r#"{"status": "err", "response": "User or API Wallet 0x0000000000000000000000000000000000000001 does not exist."}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.reject_reason(),
+            Some(OrderRejectReason::UnknownUserOrWallet)
+        );
+        assert_eq!(
+            OrderRejectReason::from_message("Vault does not exist."),
+            OrderRejectReason::Other("Vault does not exist.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_twap_responses() {
+        let response: ExchangeResponseStatus = serde_json::from_str(
+            r#"{"status": "ok", "response": {"type": "twapOrder", "data": {"status": {"running": {"twapId": 77738308}}}}}"#,
+        )
+        .unwrap();
+        let ExchangeResponseStatus::Ok(ExchangeResponse {
+            data: Some(data), ..
+        }) = response
+        else {
+            panic!("unexpected response {response:?}");
+        };
+        assert!(data.statuses.is_empty());
+        assert!(matches!(
+            data.status,
+            Some(ExchangeDataStatus::Running(RunningTwap {
+                twap_id: 77738308
+            }))
+        ));
+
+        let response: ExchangeResponseStatus = serde_json::from_str(
+            r#"{"status": "ok", "response": {"type": "twapCancel", "data": {"status": "success"}}}"#,
+        )
+        .unwrap();
+        let ExchangeResponseStatus::Ok(ExchangeResponse {
+            data: Some(data), ..
+        }) = response
+        else {
+            panic!("unexpected response {response:?}");
+        };
+        assert!(matches!(data.status, Some(ExchangeDataStatus::Success)));
+    }
+}
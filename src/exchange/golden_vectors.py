@@ -0,0 +1,98 @@
+"""Re-signs golden_vectors.json with the reference Python SDK.
+
+    pip install hyperliquid-python-sdk
+    python src/exchange/golden_vectors.py
+
+Every vector the SDK can sign gets the SDK's connection id and signature, and its `source` is set
+to `hyperliquid-python-sdk <version>`. Vectors for actions the SDK has no signer for are left as
+they are. L1 actions are hashed from the vector's msgpack so that the SDK sees the wire field
+order rather than the sorted keys of the JSON.
+"""
+
+import inspect
+import json
+import pathlib
+from importlib.metadata import version
+
+import eth_account
+import msgpack
+from hyperliquid.utils import signing
+
+TEST_KEY = "0xe908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+VECTORS = pathlib.Path(__file__).with_name("golden_vectors.json")
+
+# action type -> (SDK payload types constant, EIP-712 primary type)
+USER_SIGNED = {
+    "usdSend": ("USD_SEND_SIGN_TYPES", "HyperliquidTransaction:UsdSend"),
+    "spotSend": ("SPOT_TRANSFER_SIGN_TYPES", "HyperliquidTransaction:SpotSend"),
+    "withdraw3": ("WITHDRAW_SIGN_TYPES", "HyperliquidTransaction:Withdraw"),
+    "tokenDelegate": ("TOKEN_DELEGATE_TYPES", "HyperliquidTransaction:TokenDelegate"),
+    "convertToMultiSigUser": (
+        "CONVERT_TO_MULTI_SIG_USER_SIGN_TYPES",
+        "HyperliquidTransaction:ConvertToMultiSigUser",
+    ),
+}
+USER_SIGNED_TYPES = set(USER_SIGNED) | {"approveAgent", "approveBuilderFee", "cDeposit", "cWithdraw"}
+
+
+def with_expiry(function, *args, rest=()):
+    # releases that support expiresAfter take it right after the nonce
+    if "expires_after" in inspect.signature(function).parameters:
+        args += (None,)
+    return function(*args, *rest)
+
+
+def sign(wallet, vector):
+    action = vector["action"]
+    kind = action["type"]
+    is_mainnet = vector["isMainnet"]
+    if kind in USER_SIGNED:
+        types, primary_type = USER_SIGNED[kind]
+        if not hasattr(signing, types):
+            return None
+        return signing.sign_user_signed_action(
+            wallet, dict(action), getattr(signing, types), primary_type, is_mainnet
+        )
+    if kind == "approveAgent":
+        # unnamed agents are signed with an empty name that is left off the wire
+        return signing.sign_agent(wallet, {"agentName": "", **action}, is_mainnet)
+    if kind == "approveBuilderFee" and hasattr(signing, "sign_approve_builder_fee"):
+        return signing.sign_approve_builder_fee(wallet, dict(action), is_mainnet)
+    if kind in USER_SIGNED_TYPES or kind == "multiSig":
+        return None
+
+    wire_action = msgpack.unpackb(bytes.fromhex(vector["msgpack"]))
+    assert msgpack.packb(wire_action).hex() == vector["msgpack"], vector["name"]
+    connection_id = with_expiry(
+        signing.action_hash, wire_action, vector["vaultAddress"], vector["nonce"]
+    )
+    vector["connectionId"] = "0x" + connection_id.hex().removeprefix("0x")
+    return with_expiry(
+        signing.sign_l1_action,
+        wallet,
+        wire_action,
+        vector["vaultAddress"],
+        vector["nonce"],
+        rest=(is_mainnet,),
+    )
+
+
+def main():
+    wallet = eth_account.Account.from_key(TEST_KEY)
+    source = f"hyperliquid-python-sdk {version('hyperliquid-python-sdk')}"
+    vectors = json.loads(VECTORS.read_text())
+    for vector in vectors:
+        signature = sign(wallet, vector)
+        if signature is None:
+            print(f"skipped {vector['name']}")
+            continue
+        signature = f"{int(signature['r'], 16):064x}{int(signature['s'], 16):064x}{signature['v']:02x}"
+        if signature != vector["signature"]:
+            print(f"changed {vector['name']}")
+        vector["signature"] = signature
+        vector["source"] = source
+    VECTORS.write_text(json.dumps(vectors, indent=2) + "\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -1,18 +1,34 @@
 mod actions;
 mod builder;
 mod cancel;
+mod dead_mans_switch;
 mod exchange_client;
 mod exchange_responses;
+#[cfg(test)]
+mod golden_vectors;
 mod modify;
+mod multi_sig;
+mod nonce;
+mod normalizer;
 mod order;
+mod twap;
+mod validation;
 
 pub use actions::*;
 pub use builder::*;
 pub use cancel::{ClientCancelRequest, ClientCancelRequestCloid};
+pub use dead_mans_switch::DeadMansSwitch;
 pub use exchange_client::*;
 pub use exchange_responses::*;
 pub use modify::{ClientModifyRequest, ModifyRequest};
+pub use multi_sig::{
+    sign_multi_sig_action, sign_multi_sig_l1_action, sign_multi_sig_user_signed_action,
+};
+pub use nonce::{FileNonceProvider, InMemoryNonceProvider, NonceProvider, MAX_NONCE_AHEAD_MS};
+pub use normalizer::{AssetPrecision, OrderNormalizer};
 pub use order::{
-    ClientLimit, ClientOrder, ClientOrderRequest, ClientTrigger, MarketCloseParams,
-    MarketOrderParams, Order,
+    ClientLimit, ClientOrder, ClientOrderRequest, ClientTrigger, Grouping, MarketCloseParams,
+    MarketOrderParams, Order, Tif, TpSl,
 };
+pub use twap::{ClientTwapRequest, TwapRequest};
+pub use validation::{OrderValidationContext, OrderValidationError};
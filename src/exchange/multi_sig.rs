@@ -0,0 +1,247 @@
+use crate::{
+    exchange::exchange_client::action_hash_with_expiry, prelude::*, Actions, Error,
+    HyperliquidSigner, MultiSigUserSigned, UserSignedAction,
+};
+use ethers::types::{Signature, H160};
+
+/// Signature of one authorized user of `multi_sig_user` over `action`, to be passed to
+/// `ExchangeClient::multi_sig`. User-signed actions such as `UsdSend` are signed as typed data
+/// and everything else as an L1 action. Every signer has to use the same outer signer, `nonce`
+/// and `expires_after` as the final submission; this can be done offline.
+#[allow(clippy::too_many_arguments)]
+pub async fn sign_multi_sig_action<S: HyperliquidSigner>(
+    signer: &S,
+    action: &Actions,
+    multi_sig_user: H160,
+    outer_signer: H160,
+    vault_address: Option<H160>,
+    nonce: u64,
+    expires_after: Option<u64>,
+    is_mainnet: bool,
+) -> Result<Signature> {
+    match action {
+        Actions::UsdSend(action) => {
+            sign_multi_sig_user_signed_action(signer, action, multi_sig_user, outer_signer).await
+        }
+        Actions::Withdraw3(action) => {
+            sign_multi_sig_user_signed_action(signer, action, multi_sig_user, outer_signer).await
+        }
+        Actions::SpotSend(action) => {
+            sign_multi_sig_user_signed_action(signer, action, multi_sig_user, outer_signer).await
+        }
+        Actions::ApproveAgent(action) => {
+            sign_multi_sig_user_signed_action(signer, action, multi_sig_user, outer_signer).await
+        }
+        Actions::CDeposit(action) => {
+            sign_multi_sig_user_signed_action(signer, action, multi_sig_user, outer_signer).await
+        }
+        Actions::CWithdraw(action) => {
+            sign_multi_sig_user_signed_action(signer, action, multi_sig_user, outer_signer).await
+        }
+        Actions::TokenDelegate(action) => {
+            sign_multi_sig_user_signed_action(signer, action, multi_sig_user, outer_signer).await
+        }
+        Actions::ConvertToMultiSigUser(action) => {
+            sign_multi_sig_user_signed_action(signer, action, multi_sig_user, outer_signer).await
+        }
+        Actions::ApproveBuilderFee(action) => {
+            sign_multi_sig_user_signed_action(signer, action, multi_sig_user, outer_signer).await
+        }
+        Actions::MultiSig(_) => Err(Error::UnsupportedAction(
+            "multi-sig actions cannot be nested".to_string(),
+        )),
+        _ => {
+            sign_multi_sig_l1_action(
+                signer,
+                action,
+                multi_sig_user,
+                outer_signer,
+                vault_address,
+                nonce,
+                expires_after,
+                is_mainnet,
+            )
+            .await
+        }
+    }
+}
+
+/// Multi-sig signature over a user-signed action, which carries its own nonce and chain.
+pub async fn sign_multi_sig_user_signed_action<S: HyperliquidSigner, T: UserSignedAction>(
+    signer: &S,
+    action: &T,
+    multi_sig_user: H160,
+    outer_signer: H160,
+) -> Result<Signature> {
+    signer
+        .sign_typed_data(&MultiSigUserSigned {
+            action: action.clone(),
+            multi_sig_user,
+            outer_signer,
+        })
+        .await
+}
+
+/// Multi-sig signature over an L1 `action`. Fails for user-signed actions, which are signed
+/// with [`sign_multi_sig_user_signed_action`] instead.
+#[allow(clippy::too_many_arguments)]
+pub async fn sign_multi_sig_l1_action<S: HyperliquidSigner>(
+    signer: &S,
+    action: &Actions,
+    multi_sig_user: H160,
+    outer_signer: H160,
+    vault_address: Option<H160>,
+    nonce: u64,
+    expires_after: Option<u64>,
+    is_mainnet: bool,
+) -> Result<Signature> {
+    if action.is_user_signed() || matches!(action, Actions::MultiSig(_)) {
+        return Err(Error::UnsupportedAction(
+            "only L1 actions are signed over their connection id".to_string(),
+        ));
+    }
+    let envelope = (multi_sig_user, outer_signer, action);
+    let connection_id = action_hash_with_expiry(&envelope, nonce, vault_address, expires_after)?;
+    signer.sign_l1_action(connection_id, is_mainnet).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        action_hash,
+        exchange::{actions::BulkCancel, cancel::CancelRequest},
+        signature::{recover_typed_data_signer, sign_typed_data},
+        ApproveBuilderFee, Error, MultiSig, MultiSigPayload, SendMultiSig, UsdSend,
+    };
+    use ethers::{signers::LocalWallet, types::transaction::eip712::Eip712, utils::keccak256};
+
+    fn get_wallet(priv_key: &str) -> Result<LocalWallet> {
+        priv_key
+            .parse::<LocalWallet>()
+            .map_err(|e| Error::Wallet(e.to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_multi_sig_l1_action_signing() -> Result<()> {
+        let outer = get_wallet("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e")?;
+        let cosigner =
+            get_wallet("0123456789012345678901234567890123456789012345678901234567890123")?;
+        let multi_sig_user: H160 = "0x0000000000000000000000000000000000000005"
+            .parse()
+            .unwrap();
+        let action = Actions::Cancel(BulkCancel {
+            cancels: vec![CancelRequest {
+                asset: 1,
+                oid: 82382,
+            }],
+        });
+
+        let mut signatures = Vec::new();
+        for wallet in [&outer, &cosigner] {
+            signatures.push(
+                sign_multi_sig_l1_action(
+                    wallet,
+                    &action,
+                    multi_sig_user,
+                    outer.address(),
+                    None,
+                    1583838,
+                    None,
+                    false,
+                )
+                .await?,
+            );
+        }
+        assert_eq!(signatures[0].to_string(), "2cce4b1971713a0a2aab53df7d09309de2b3d92dd14d49f4e46e0420acf14e77355fb4f1b93dd935c8470196eb98234e4ca9ecd9d07edc11443a66434b5d08b41b");
+        assert_eq!(signatures[1].to_string(), "f14f56b51f3af6d0b4e3a1b295708e095577fe5fea1a84799bba4c77ac613cbf6ea0613f00bb8f349a116db8e912cd0c7363d67355b17187eca6835f252b29111b");
+
+        let multi_sig = MultiSig {
+            signature_chain_id: 421614.into(),
+            signatures,
+            payload: MultiSigPayload {
+                multi_sig_user,
+                outer_signer: outer.address(),
+                action: Box::new(action),
+            },
+        };
+        let value = serde_json::to_value(Actions::MultiSig(multi_sig.clone()))
+            .map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert_eq!(value["type"], "multiSig");
+        assert_eq!(value["payload"]["action"]["type"], "cancel");
+        assert_eq!(value["signatures"][0]["v"], 27);
+
+        let envelope = SendMultiSig {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            multi_sig_action_hash: action_hash(&multi_sig, 1583838, None)?,
+            nonce: 1583838,
+        };
+        assert_eq!(sign_typed_data(&envelope, &outer)?.to_string(), "fec5e4af7dba709daee2bc54d2256e4a44931af545ee8a9e0112af91a2b7e6df1c46efef3fe37ad0a867bfa503686c5e289a2cd7160ac8e56cf88dbcc33bdbbc1b");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multi_sig_user_signed_action_signing() -> Result<()> {
+        let wallet =
+            get_wallet("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e")?;
+        let multi_sig_user: H160 = "0x0000000000000000000000000000000000000005"
+            .parse()
+            .unwrap();
+        let usd_send = UsdSend {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            destination: "0x0D1d9635D0640821d15e323ac8AdADfA9c111414".to_string(),
+            amount: "1".to_string(),
+            time: 1690393044548,
+        };
+        let action = Actions::UsdSend(usd_send.clone());
+
+        let signature = sign_multi_sig_action(
+            &wallet,
+            &action,
+            multi_sig_user,
+            wallet.address(),
+            None,
+            1690393044548,
+            None,
+            false,
+        )
+        .await?;
+        assert_eq!(signature.to_string(), "f8a6931defe3bbc051ca109b6dabc1ed2bdbc65bfcea2049d085239bce2c893b78dea379eac25daa4f80f0baa250222f120938216aeaa6a57fc2330d329da9ed1b");
+
+        let typed = MultiSigUserSigned {
+            action: usd_send,
+            multi_sig_user,
+            outer_signer: wallet.address(),
+        };
+        assert_eq!(
+            MultiSigUserSigned::<UsdSend>::type_hash().unwrap(),
+            keccak256("HyperliquidTransaction:UsdSend(string hyperliquidChain,address payloadMultiSigUser,address outerSigner,string destination,string amount,uint64 time)")
+        );
+        assert_eq!(
+            MultiSigUserSigned::<ApproveBuilderFee>::type_hash().unwrap(),
+            keccak256("HyperliquidTransaction:ApproveBuilderFee(string hyperliquidChain,address payloadMultiSigUser,address outerSigner,string maxFeeRate,address builder,uint64 nonce)")
+        );
+        assert_eq!(
+            recover_typed_data_signer(&typed, &signature)?,
+            wallet.address()
+        );
+
+        assert!(matches!(
+            sign_multi_sig_l1_action(
+                &wallet,
+                &action,
+                multi_sig_user,
+                wallet.address(),
+                None,
+                1690393044548,
+                None,
+                false,
+            )
+            .await,
+            Err(Error::UnsupportedAction(_))
+        ));
+        Ok(())
+    }
+}
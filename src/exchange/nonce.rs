@@ -0,0 +1,176 @@
+use crate::{helpers::now_timestamp_ms, prelude::*, Error};
+use ethers::types::H160;
+use fs2::FileExt;
+use lazy_static::lazy_static;
+use log::info;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+/// Nonces may be at most a day ahead of the block time.
+pub const MAX_NONCE_AHEAD_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Hands out the nonces of exchange actions.
+///
+/// Hyperliquid keeps the 100 highest nonces of each signer and rejects nonces that were already
+/// used or are lower than all of them, so nonces must be unique per signer and should increase.
+pub trait NonceProvider: Debug + Send + Sync {
+    fn next_nonce(&self, signer: H160) -> Result<u64>;
+}
+
+/// The current time in milliseconds, or one more than the last nonce if that is later.
+fn next_after(last: Option<u64>) -> Result<u64> {
+    let now_ms = now_timestamp_ms();
+    let nonce = last.map_or(now_ms, |last| now_ms.max(last + 1));
+    if nonce > now_ms + MAX_NONCE_AHEAD_MS {
+        return Err(Error::Nonce(format!(
+            "nonce {nonce} is too far ahead of the current time {now_ms}"
+        )));
+    }
+    if nonce > now_ms + 1000 {
+        info!("nonce progressed too far ahead {nonce} {now_ms}");
+    }
+    Ok(nonce)
+}
+
+/// Tracks the last nonce of each signer in memory. Clients that share a key in one process
+/// should share one provider; `ExchangeClient`s use [`InMemoryNonceProvider::shared`] by default.
+#[derive(Debug, Default)]
+pub struct InMemoryNonceProvider {
+    last_nonces: Mutex<HashMap<H160, u64>>,
+}
+
+lazy_static! {
+    static ref SHARED_PROVIDER: Arc<InMemoryNonceProvider> = Arc::default();
+}
+
+impl InMemoryNonceProvider {
+    /// The provider shared by every client of this process.
+    pub fn shared() -> Arc<InMemoryNonceProvider> {
+        SHARED_PROVIDER.clone()
+    }
+}
+
+impl NonceProvider for InMemoryNonceProvider {
+    fn next_nonce(&self, signer: H160) -> Result<u64> {
+        let mut last_nonces = self
+            .last_nonces
+            .lock()
+            .map_err(|e| Error::Nonce(e.to_string()))?;
+        let nonce = next_after(last_nonces.get(&signer).copied())?;
+        last_nonces.insert(signer, nonce);
+        Ok(nonce)
+    }
+}
+
+/// Keeps the last nonce of each signer in a JSON file, locked while it is updated, so that
+/// processes sharing a key never reuse a nonce, including across restarts.
+#[derive(Debug, Clone)]
+pub struct FileNonceProvider {
+    path: PathBuf,
+}
+
+impl FileNonceProvider {
+    pub fn new(path: impl Into<PathBuf>) -> FileNonceProvider {
+        FileNonceProvider { path: path.into() }
+    }
+
+    fn update(&self, file: &mut File, signer: H160) -> Result<u64> {
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .map_err(|e| Error::Nonce(e.to_string()))?;
+        let mut last_nonces: HashMap<H160, u64> = if contents.trim().is_empty() {
+            HashMap::new()
+        } else {
+            serde_json::from_str(&contents).map_err(|e| Error::JsonParse(e.to_string()))?
+        };
+
+        let nonce = next_after(last_nonces.get(&signer).copied())?;
+        last_nonces.insert(signer, nonce);
+
+        let contents =
+            serde_json::to_vec(&last_nonces).map_err(|e| Error::JsonParse(e.to_string()))?;
+        file.seek(SeekFrom::Start(0))
+            .and_then(|_| file.set_len(0))
+            .and_then(|_| file.write_all(&contents))
+            .and_then(|_| file.sync_data())
+            .map_err(|e| Error::Nonce(e.to_string()))?;
+        Ok(nonce)
+    }
+}
+
+impl NonceProvider for FileNonceProvider {
+    fn next_nonce(&self, signer: H160) -> Result<u64> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)
+            .map_err(|e| Error::Nonce(format!("Could not open {}: {e}", self.path.display())))?;
+        file.lock_exclusive()
+            .map_err(|e| Error::Nonce(e.to_string()))?;
+        let nonce = self.update(&mut file, signer);
+        // not `file.unlock()`, which resolves to the inherent `File::unlock` on newer toolchains
+        let unlocked = FileExt::unlock(&file).map_err(|e| Error::Nonce(e.to_string()));
+        // a failed update is the more useful error
+        let nonce = nonce?;
+        unlocked?;
+        Ok(nonce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Removes the nonce file when the test ends, even if it fails early.
+    struct TempFile(PathBuf);
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_nonces_are_unique_per_signer() -> Result<()> {
+        let file =
+            TempFile(std::env::temp_dir().join(format!("hl-nonces-{}.json", std::process::id())));
+        let (alice, bob) = (H160::from_low_u64_be(1), H160::from_low_u64_be(2));
+        // alice's last nonce is an hour ahead, which must not hold back bob's nonces
+        let ahead = now_timestamp_ms() + 60 * 60 * 1000;
+
+        let in_memory = InMemoryNonceProvider::default();
+        in_memory
+            .last_nonces
+            .lock()
+            .map_err(|e| Error::Nonce(e.to_string()))?
+            .insert(alice, ahead);
+        let seeded = serde_json::to_string(&HashMap::from([(alice, ahead)]))
+            .map_err(|e| Error::JsonParse(e.to_string()))?;
+        std::fs::write(&file.0, seeded).map_err(|e| Error::Nonce(e.to_string()))?;
+
+        let providers: [Box<dyn NonceProvider>; 2] = [
+            Box::new(in_memory),
+            Box::new(FileNonceProvider::new(&file.0)),
+        ];
+        for provider in providers {
+            assert_eq!(provider.next_nonce(alice)?, ahead + 1);
+            assert_eq!(provider.next_nonce(alice)?, ahead + 2);
+            let first = provider.next_nonce(bob)?;
+            assert!(first < ahead);
+            assert!(provider.next_nonce(bob)? > first);
+        }
+
+        // a new provider, as after a restart, continues after the persisted nonces
+        let restarted = FileNonceProvider::new(&file.0);
+        assert_eq!(restarted.next_nonce(alice)?, ahead + 3);
+        Ok(())
+    }
+}
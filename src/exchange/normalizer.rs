@@ -0,0 +1,259 @@
+use crate::{
+    meta::{perp_dex_coin, Meta, SpotMeta},
+    prelude::*,
+    ClientOrder, ClientOrderRequest, Decimal, Error, RoundingMode,
+};
+use std::collections::HashMap;
+
+const PERP_MAX_DECIMALS: u32 = 6;
+const SPOT_MAX_DECIMALS: u32 = 8;
+const MAX_SIGNIFICANT_FIGURES: u32 = 5;
+
+/// Tick and lot size rules of a single asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetPrecision {
+    pub sz_decimals: u32,
+    /// 6 for perps and 8 for spot.
+    pub max_decimals: u32,
+}
+
+impl AssetPrecision {
+    pub fn price_decimals(&self) -> u32 {
+        self.max_decimals.saturating_sub(self.sz_decimals)
+    }
+
+    /// Prices may have at most 5 significant figures and `price_decimals` decimals, except
+    /// that integer prices are always allowed.
+    pub fn is_valid_price(&self, px: Decimal) -> bool {
+        px.scale() == 0
+            || (px.significant_figures() <= MAX_SIGNIFICANT_FIGURES
+                && px.scale() <= self.price_decimals())
+    }
+
+    pub fn is_valid_size(&self, sz: Decimal) -> bool {
+        sz.scale() <= self.sz_decimals
+    }
+
+    pub fn round_price(&self, px: Decimal, mode: RoundingMode) -> Result<Decimal> {
+        if self.is_valid_price(px) {
+            return Ok(px);
+        }
+        Ok(px
+            .round_sf_with(MAX_SIGNIFICANT_FIGURES, mode)?
+            .round_dp_with(self.price_decimals(), mode))
+    }
+
+    pub fn round_size(&self, sz: Decimal, mode: RoundingMode) -> Decimal {
+        sz.round_dp_with(self.sz_decimals, mode)
+    }
+}
+
+/// Rounds and validates order prices and sizes according to the `szDecimals` published in
+/// `Meta` (perps) and `SpotMeta` (spot, using the base token).
+#[derive(Debug, Clone, Default)]
+pub struct OrderNormalizer {
+    precisions: HashMap<String, AssetPrecision>,
+    pub price_rounding: RoundingMode,
+    pub size_rounding: RoundingMode,
+}
+
+impl OrderNormalizer {
+    pub fn new(meta: &Meta, spot_meta: &SpotMeta) -> OrderNormalizer {
+        let mut precisions = HashMap::new();
+        for asset in meta.universe.iter() {
+            precisions.insert(
+                asset.name.clone(),
+                AssetPrecision {
+                    sz_decimals: asset.sz_decimals,
+                    max_decimals: PERP_MAX_DECIMALS,
+                },
+            );
+        }
+
+        let index_to_token: HashMap<usize, _> = spot_meta
+            .tokens
+            .iter()
+            .map(|info| (info.index, info))
+            .collect();
+        for asset in spot_meta.universe.iter() {
+            let (Some(base), Some(quote)) = (
+                index_to_token.get(&asset.tokens[0]),
+                index_to_token.get(&asset.tokens[1]),
+            ) else {
+                continue;
+            };
+            let precision = AssetPrecision {
+                sz_decimals: base.sz_decimals as u32,
+                max_decimals: SPOT_MAX_DECIMALS,
+            };
+            precisions.insert(format!("{}/{}", base.name, quote.name), precision);
+            precisions.insert(asset.name.clone(), precision);
+        }
+
+        OrderNormalizer {
+            precisions,
+            price_rounding: RoundingMode::Nearest,
+            size_rounding: RoundingMode::Nearest,
+        }
+    }
+
+    /// Adds the assets of a builder-deployed perp dex, named `dex:COIN`.
+    pub fn add_perp_dex(&mut self, dex: &str, meta: &Meta) {
+        for asset in meta.universe.iter() {
+            self.precisions.insert(
+                perp_dex_coin(dex, &asset.name),
+                AssetPrecision {
+                    sz_decimals: asset.sz_decimals,
+                    max_decimals: PERP_MAX_DECIMALS,
+                },
+            );
+        }
+    }
+
+    pub fn with_rounding(
+        mut self,
+        price_rounding: RoundingMode,
+        size_rounding: RoundingMode,
+    ) -> Self {
+        self.price_rounding = price_rounding;
+        self.size_rounding = size_rounding;
+        self
+    }
+
+    pub fn precision(&self, coin: &str) -> Result<AssetPrecision> {
+        self.precisions
+            .get(coin)
+            .copied()
+            .ok_or(Error::AssetNotFound)
+    }
+
+    pub fn round_price(&self, coin: &str, px: Decimal) -> Result<Decimal> {
+        self.precision(coin)?.round_price(px, self.price_rounding)
+    }
+
+    pub fn round_size(&self, coin: &str, sz: Decimal) -> Result<Decimal> {
+        Ok(self.precision(coin)?.round_size(sz, self.size_rounding))
+    }
+
+    pub fn validate_price(&self, coin: &str, px: Decimal) -> Result<()> {
+        if self.precision(coin)?.is_valid_price(px) {
+            Ok(())
+        } else {
+            Err(Error::InvalidPrice(px.to_string()))
+        }
+    }
+
+    pub fn validate_size(&self, coin: &str, sz: Decimal) -> Result<()> {
+        if self.precision(coin)?.is_valid_size(sz) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSize(sz.to_string()))
+        }
+    }
+
+    /// Rounds the limit price, trigger price and size of an order onto the asset's grid.
+    pub fn normalize(&self, mut order: ClientOrderRequest) -> Result<ClientOrderRequest> {
+        let precision = self.precision(&order.asset)?;
+        order.limit_px = precision.round_price(order.limit_px, self.price_rounding)?;
+        order.sz = precision.round_size(order.sz, self.size_rounding);
+        if let ClientOrder::Trigger(trigger) = &mut order.order_type {
+            trigger.trigger_px = precision.round_price(trigger.trigger_px, self.price_rounding)?;
+        }
+        Ok(order)
+    }
+
+    pub fn validate(&self, order: &ClientOrderRequest) -> Result<()> {
+        self.validate_price(&order.asset, order.limit_px)?;
+        self.validate_size(&order.asset, order.sz)?;
+        if let ClientOrder::Trigger(trigger) = &order.order_type {
+            self.validate_price(&order.asset, trigger.trigger_px)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientLimit, Tif};
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn normalizer() -> OrderNormalizer {
+        let meta: Meta = serde_json::from_str(
+            r#"{"universe": [{"name": "BTC", "szDecimals": 5}, {"name": "ETH", "szDecimals": 4}]}"#,
+        )
+        .unwrap();
+        let spot_meta: SpotMeta = serde_json::from_str(
+            r#"{
+                "universe": [{"tokens": [1, 0], "name": "PURR/USDC", "index": 0, "isCanonical": true}],
+                "tokens": [
+                    {"name": "USDC", "szDecimals": 8, "weiDecimals": 8, "index": 0, "tokenId": "0x6d1e7cde53ba9467b783cb7c530ce054", "isCanonical": true},
+                    {"name": "PURR", "szDecimals": 0, "weiDecimals": 5, "index": 1, "tokenId": "0xc1fb593aeffbeb02f85e0308e9956a90", "isCanonical": true}
+                ]
+            }"#,
+        )
+        .unwrap();
+        OrderNormalizer::new(&meta, &spot_meta)
+    }
+
+    #[test]
+    fn perp_price_and_size_rules() -> Result<()> {
+        let normalizer = normalizer();
+        assert_eq!(normalizer.precision("ETH")?.price_decimals(), 2);
+
+        assert_eq!(
+            normalizer.round_price("ETH", dec("1800.123"))?,
+            dec("1800.1")
+        );
+        assert_eq!(normalizer.round_price("ETH", dec("12.3456"))?, dec("12.35"));
+        assert_eq!(normalizer.round_price("BTC", dec("65432.1"))?, dec("65432"));
+        // integer prices are always valid
+        assert_eq!(normalizer.round_price("BTC", dec("123456"))?, dec("123456"));
+        assert_eq!(
+            normalizer.round_size("BTC", dec("0.123456"))?,
+            dec("0.12346")
+        );
+
+        assert!(normalizer.validate_price("ETH", dec("1800.1")).is_ok());
+        assert!(normalizer.validate_price("ETH", dec("1800.12")).is_err());
+        assert!(normalizer.validate_size("ETH", dec("0.12345")).is_err());
+        assert!(normalizer.validate_size("DOGE", dec("1")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn spot_price_and_size_rules() -> Result<()> {
+        let normalizer = normalizer();
+        assert_eq!(normalizer.precision("PURR/USDC")?.price_decimals(), 8);
+        assert_eq!(
+            normalizer.round_price("PURR/USDC", dec("0.0000237809"))?,
+            dec("0.00002378")
+        );
+        assert_eq!(
+            normalizer.round_size("PURR/USDC", dec("1000.6"))?,
+            dec("1001")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn directional_rounding() -> Result<()> {
+        let normalizer = normalizer().with_rounding(RoundingMode::Down, RoundingMode::Down);
+        let order = normalizer.normalize(ClientOrderRequest {
+            asset: "ETH".to_string(),
+            is_buy: true,
+            reduce_only: false,
+            limit_px: dec("1800.19"),
+            sz: dec("0.01239"),
+            cloid: None,
+            order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Gtc }),
+        })?;
+        assert_eq!(order.limit_px, dec("1800.1"));
+        assert_eq!(order.sz, dec("0.0123"));
+        assert!(normalizer.validate(&order).is_ok());
+        Ok(())
+    }
+}
@@ -1,16 +1,42 @@
-use crate::{
-    errors::Error,
-    helpers::{float_to_string_for_hashing, uuid_to_hex_string},
-    prelude::*,
-};
+use crate::{errors::Error, helpers::uuid_to_hex_string, prelude::*, Decimal};
 use ethers::signers::LocalWallet;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tif {
+    /// Add liquidity only (post only)
+    Alo,
+    /// Immediate or cancel
+    Ioc,
+    /// Good til cancelled
+    Gtc,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TpSl {
+    Tp,
+    Sl,
+}
+
+/// How the orders of a bulk order relate to each other.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Grouping {
+    /// Independent orders
+    #[default]
+    Na,
+    /// An entry order followed by take profit and/or stop loss orders sized to the entry
+    NormalTpsl,
+    /// Take profit and/or stop loss orders attached to the whole position
+    PositionTpsl,
+}
+
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct Limit {
-    pub tif: String,
+    pub tif: Tif,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -18,7 +44,7 @@ pub struct Limit {
 pub struct Trigger {
     pub is_market: bool,
     pub trigger_px: String,
-    pub tpsl: String,
+    pub tpsl: TpSl,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -49,35 +75,35 @@ pub struct OrderRequest {
 
 #[derive(Debug)]
 pub struct ClientLimit {
-    pub tif: String,
+    pub tif: Tif,
 }
 
 #[derive(Debug)]
 pub struct ClientTrigger {
     pub is_market: bool,
-    pub trigger_px: f64,
-    pub tpsl: String,
+    pub trigger_px: Decimal,
+    pub tpsl: TpSl,
 }
 
 #[derive(Debug)]
-pub struct MarketOrderParams<'a> {
+pub struct MarketOrderParams<'a, S = LocalWallet> {
     pub asset: &'a str,
     pub is_buy: bool,
-    pub sz: f64,
-    pub px: Option<f64>,
+    pub sz: Decimal,
+    pub px: Option<Decimal>,
     pub slippage: Option<f64>,
     pub cloid: Option<Uuid>,
-    pub wallet: Option<&'a LocalWallet>,
+    pub wallet: Option<&'a S>,
 }
 
 #[derive(Debug)]
-pub struct MarketCloseParams<'a> {
+pub struct MarketCloseParams<'a, S = LocalWallet> {
     pub asset: &'a str,
-    pub sz: Option<f64>,
-    pub px: Option<f64>,
+    pub sz: Option<Decimal>,
+    pub px: Option<Decimal>,
     pub slippage: Option<f64>,
     pub cloid: Option<Uuid>,
-    pub wallet: Option<&'a LocalWallet>,
+    pub wallet: Option<&'a S>,
 }
 
 #[derive(Debug)]
@@ -91,8 +117,8 @@ pub struct ClientOrderRequest {
     pub asset: String,
     pub is_buy: bool,
     pub reduce_only: bool,
-    pub limit_px: f64,
-    pub sz: f64,
+    pub limit_px: Decimal,
+    pub sz: Decimal,
     pub cloid: Option<Uuid>,
     pub order_type: ClientOrder,
 }
@@ -102,7 +128,7 @@ impl ClientOrderRequest {
         let order_type = match self.order_type {
             ClientOrder::Limit(limit) => Order::Limit(Limit { tif: limit.tif }),
             ClientOrder::Trigger(trigger) => Order::Trigger(Trigger {
-                trigger_px: float_to_string_for_hashing(trigger.trigger_px),
+                trigger_px: trigger.trigger_px.to_string(),
                 is_market: trigger.is_market,
                 tpsl: trigger.tpsl,
             }),
@@ -115,10 +141,57 @@ impl ClientOrderRequest {
             asset,
             is_buy: self.is_buy,
             reduce_only: self.reduce_only,
-            limit_px: float_to_string_for_hashing(self.limit_px),
-            sz: float_to_string_for_hashing(self.sz),
+            limit_px: self.limit_px.to_string(),
+            sz: self.sz.to_string(),
             order_type,
             cloid,
         })
     }
+
+    /// Builds the `[entry, tp, sl]` orders of a `Grouping::NormalTpsl` bulk order. The take
+    /// profit and stop loss are reduce-only market triggers on the opposite side of the entry.
+    pub fn with_tpsl(self, tp: Option<Decimal>, sl: Option<Decimal>) -> Vec<ClientOrderRequest> {
+        let children = tpsl_triggers(&self.asset, !self.is_buy, self.sz, tp, sl);
+        let mut orders = vec![self];
+        orders.extend(children);
+        orders
+    }
+
+    /// Builds the `[tp, sl]` orders of a `Grouping::PositionTpsl` bulk order for a position of
+    /// signed size `szi`, as reduce-only market triggers closing the whole position.
+    pub fn position_tpsl(
+        asset: &str,
+        szi: Decimal,
+        tp: Option<Decimal>,
+        sl: Option<Decimal>,
+    ) -> Vec<ClientOrderRequest> {
+        tpsl_triggers(asset, szi.is_negative(), szi.abs(), tp, sl)
+    }
+}
+
+fn tpsl_triggers(
+    asset: &str,
+    is_buy: bool,
+    sz: Decimal,
+    tp: Option<Decimal>,
+    sl: Option<Decimal>,
+) -> Vec<ClientOrderRequest> {
+    [(tp, TpSl::Tp), (sl, TpSl::Sl)]
+        .into_iter()
+        .filter_map(|(trigger_px, tpsl)| {
+            trigger_px.map(|trigger_px| ClientOrderRequest {
+                asset: asset.to_string(),
+                is_buy,
+                reduce_only: true,
+                limit_px: trigger_px,
+                sz,
+                cloid: None,
+                order_type: ClientOrder::Trigger(ClientTrigger {
+                    is_market: true,
+                    trigger_px,
+                    tpsl,
+                }),
+            })
+        })
+        .collect()
 }
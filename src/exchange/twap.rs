@@ -0,0 +1,30 @@
+use crate::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// A TWAP order executed by the exchange in slices over `minutes` minutes.
+#[derive(Debug)]
+pub struct ClientTwapRequest {
+    pub asset: String,
+    pub is_buy: bool,
+    pub sz: Decimal,
+    pub reduce_only: bool,
+    pub minutes: u64,
+    /// Randomize the timing of the slices
+    pub randomize: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TwapRequest {
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: u32,
+    #[serde(rename = "b", alias = "isBuy")]
+    pub is_buy: bool,
+    #[serde(rename = "s", alias = "sz")]
+    pub sz: String,
+    #[serde(rename = "r", alias = "reduceOnly")]
+    pub reduce_only: bool,
+    #[serde(rename = "m", alias = "minutes")]
+    pub minutes: u64,
+    #[serde(rename = "t", alias = "randomize")]
+    pub randomize: bool,
+}
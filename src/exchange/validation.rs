@@ -0,0 +1,310 @@
+use crate::{ClientModifyRequest, ClientOrder, ClientOrderRequest, Decimal, TpSl};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Reasons an order is rejected locally, before it is signed and sent to `/exchange`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum OrderValidationError {
+    #[error("Unknown asset {0}")]
+    UnknownAsset(String),
+    #[error("Order size must be positive, got {0}")]
+    NonPositiveSize(Decimal),
+    #[error("Order price must be positive, got {0}")]
+    NonPositivePrice(Decimal),
+    #[error("Order notional of {sz} at {limit_px} is out of range")]
+    NotionalOverflow { limit_px: Decimal, sz: Decimal },
+    #[error("Order notional {notional} is below the minimum of {min_notional}")]
+    BelowMinNotional {
+        notional: Decimal,
+        min_notional: Decimal,
+    },
+    #[error("Reduce-only is not supported for spot asset {0}")]
+    ReduceOnlySpot(String),
+    #[error("Reduce-only order on {0} has no open position to reduce")]
+    ReduceOnlyWithoutPosition(String),
+    #[error("Reduce-only order on {0} would increase the position")]
+    ReduceOnlyWrongSide(String),
+    #[error("Trigger price must be positive, got {0}")]
+    NonPositiveTriggerPrice(Decimal),
+    #[error(
+        "Trigger price {trigger_px} would fire immediately against reference price {reference_px}"
+    )]
+    TriggerPriceAlreadyCrossed {
+        trigger_px: Decimal,
+        reference_px: Decimal,
+    },
+}
+
+/// Market data used by [`ClientOrderRequest::validate`]. Only `coin_to_asset` is required;
+/// position and reference price checks are skipped when the maps are not provided.
+#[derive(Debug, Clone)]
+pub struct OrderValidationContext<'a> {
+    pub coin_to_asset: &'a HashMap<String, u32>,
+    pub min_notional: Decimal,
+    /// Signed position size per coin, as in `PositionData::szi`.
+    pub positions: Option<&'a HashMap<String, Decimal>>,
+    /// Current price per coin, usually the mid or mark price.
+    pub reference_prices: Option<&'a HashMap<String, Decimal>>,
+}
+
+impl<'a> OrderValidationContext<'a> {
+    pub const DEFAULT_MIN_NOTIONAL: u64 = 10;
+
+    pub fn new(coin_to_asset: &'a HashMap<String, u32>) -> Self {
+        OrderValidationContext {
+            coin_to_asset,
+            min_notional: Decimal::from(Self::DEFAULT_MIN_NOTIONAL),
+            positions: None,
+            reference_prices: None,
+        }
+    }
+}
+
+impl ClientOrderRequest {
+    pub fn validate(
+        &self,
+        context: &OrderValidationContext<'_>,
+    ) -> Result<(), OrderValidationError> {
+        let &asset = context
+            .coin_to_asset
+            .get(&self.asset)
+            .ok_or_else(|| OrderValidationError::UnknownAsset(self.asset.clone()))?;
+
+        if !self.sz.is_positive() {
+            return Err(OrderValidationError::NonPositiveSize(self.sz));
+        }
+        if !self.limit_px.is_positive() {
+            return Err(OrderValidationError::NonPositivePrice(self.limit_px));
+        }
+        let notional =
+            self.limit_px
+                .checked_mul(self.sz)
+                .ok_or(OrderValidationError::NotionalOverflow {
+                    limit_px: self.limit_px,
+                    sz: self.sz,
+                })?;
+        if notional < context.min_notional {
+            return Err(OrderValidationError::BelowMinNotional {
+                notional,
+                min_notional: context.min_notional,
+            });
+        }
+
+        if self.reduce_only {
+            // spot assets sit between the default perps and the builder-deployed perp dexs
+            if (10000..100000).contains(&asset) {
+                return Err(OrderValidationError::ReduceOnlySpot(self.asset.clone()));
+            }
+            if let Some(positions) = context.positions {
+                let szi = positions.get(&self.asset).copied().unwrap_or_default();
+                if szi.is_zero() {
+                    return Err(OrderValidationError::ReduceOnlyWithoutPosition(
+                        self.asset.clone(),
+                    ));
+                }
+                if self.is_buy != szi.is_negative() {
+                    return Err(OrderValidationError::ReduceOnlyWrongSide(
+                        self.asset.clone(),
+                    ));
+                }
+            }
+        }
+
+        if let ClientOrder::Trigger(trigger) = &self.order_type {
+            let is_tp = trigger.tpsl == TpSl::Tp;
+            let trigger_px = trigger.trigger_px;
+            if !trigger_px.is_positive() {
+                return Err(OrderValidationError::NonPositiveTriggerPrice(trigger_px));
+            }
+            // buy take profits and sell stop losses fire when the price falls to the trigger
+            let fires_below = self.is_buy == is_tp;
+            if let Some(&reference_px) = context
+                .reference_prices
+                .and_then(|prices| prices.get(&self.asset))
+            {
+                let already_crossed = if fires_below {
+                    reference_px <= trigger_px
+                } else {
+                    reference_px >= trigger_px
+                };
+                if already_crossed {
+                    return Err(OrderValidationError::TriggerPriceAlreadyCrossed {
+                        trigger_px,
+                        reference_px,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ClientModifyRequest {
+    pub fn validate(
+        &self,
+        context: &OrderValidationContext<'_>,
+    ) -> Result<(), OrderValidationError> {
+        self.order.validate(context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientLimit, ClientTrigger, Tif};
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn coin_to_asset() -> HashMap<String, u32> {
+        HashMap::from([("ETH".to_string(), 1), ("PURR/USDC".to_string(), 10000)])
+    }
+
+    fn limit_order(asset: &str, is_buy: bool, limit_px: &str, sz: &str) -> ClientOrderRequest {
+        ClientOrderRequest {
+            asset: asset.to_string(),
+            is_buy,
+            reduce_only: false,
+            limit_px: dec(limit_px),
+            sz: dec(sz),
+            cloid: None,
+            order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Gtc }),
+        }
+    }
+
+    fn trigger_order(
+        is_buy: bool,
+        limit_px: &str,
+        trigger_px: &str,
+        tpsl: TpSl,
+    ) -> ClientOrderRequest {
+        ClientOrderRequest {
+            order_type: ClientOrder::Trigger(ClientTrigger {
+                is_market: false,
+                trigger_px: dec(trigger_px),
+                tpsl,
+            }),
+            ..limit_order("ETH", is_buy, limit_px, "1")
+        }
+    }
+
+    #[test]
+    fn test_basic_order_checks() {
+        let coin_to_asset = coin_to_asset();
+        let context = OrderValidationContext::new(&coin_to_asset);
+
+        assert!(limit_order("ETH", true, "1800", "0.01")
+            .validate(&context)
+            .is_ok());
+        assert_eq!(
+            limit_order("DOGE", true, "1800", "0.01").validate(&context),
+            Err(OrderValidationError::UnknownAsset("DOGE".to_string()))
+        );
+        assert_eq!(
+            limit_order("ETH", true, "1800", "0").validate(&context),
+            Err(OrderValidationError::NonPositiveSize(dec("0")))
+        );
+        assert_eq!(
+            limit_order("ETH", true, "-1", "1").validate(&context),
+            Err(OrderValidationError::NonPositivePrice(dec("-1")))
+        );
+        assert_eq!(
+            limit_order("ETH", true, "1800", "0.005").validate(&context),
+            Err(OrderValidationError::BelowMinNotional {
+                notional: dec("9"),
+                min_notional: dec("10"),
+            })
+        );
+        let huge = dec("123456789012345678901");
+        assert_eq!(
+            limit_order(
+                "ETH",
+                true,
+                "123456789012345678901",
+                "123456789012345678901"
+            )
+            .validate(&context),
+            Err(OrderValidationError::NotionalOverflow {
+                limit_px: huge,
+                sz: huge,
+            })
+        );
+    }
+
+    #[test]
+    fn test_reduce_only_checks() {
+        let coin_to_asset = coin_to_asset();
+        let positions = HashMap::from([("ETH".to_string(), dec("-0.5"))]);
+        let mut context = OrderValidationContext::new(&coin_to_asset);
+
+        let mut spot_order = limit_order("PURR/USDC", false, "0.1", "1000");
+        spot_order.reduce_only = true;
+        assert_eq!(
+            spot_order.validate(&context),
+            Err(OrderValidationError::ReduceOnlySpot(
+                "PURR/USDC".to_string()
+            ))
+        );
+
+        let mut sell = limit_order("ETH", false, "1800", "0.01");
+        sell.reduce_only = true;
+        // without positions the side cannot be checked
+        assert!(sell.validate(&context).is_ok());
+
+        context.positions = Some(&positions);
+        assert_eq!(
+            sell.validate(&context),
+            Err(OrderValidationError::ReduceOnlyWrongSide("ETH".to_string()))
+        );
+        sell.is_buy = true;
+        assert!(sell.validate(&context).is_ok());
+
+        let empty = HashMap::new();
+        context.positions = Some(&empty);
+        assert_eq!(
+            sell.validate(&context),
+            Err(OrderValidationError::ReduceOnlyWithoutPosition(
+                "ETH".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_trigger_checks() {
+        let coin_to_asset = coin_to_asset();
+        let reference_prices = HashMap::from([("ETH".to_string(), dec("2000"))]);
+        let mut context = OrderValidationContext::new(&coin_to_asset);
+
+        // once triggered, a limit beyond the trigger price simply rests on the book
+        assert!(trigger_order(false, "1950", "1900", TpSl::Sl)
+            .validate(&context)
+            .is_ok());
+
+        context.reference_prices = Some(&reference_prices);
+        // sell stop loss below the market and sell take profit above it are fine
+        assert!(trigger_order(false, "1890", "1900", TpSl::Sl)
+            .validate(&context)
+            .is_ok());
+        assert!(trigger_order(false, "2090", "2100", TpSl::Tp)
+            .validate(&context)
+            .is_ok());
+        assert_eq!(
+            trigger_order(false, "2090", "2100", TpSl::Sl).validate(&context),
+            Err(OrderValidationError::TriggerPriceAlreadyCrossed {
+                trigger_px: dec("2100"),
+                reference_px: dec("2000"),
+            })
+        );
+        // buy stop loss above the market and buy take profit below it are fine
+        assert!(trigger_order(true, "2110", "2100", TpSl::Sl)
+            .validate(&context)
+            .is_ok());
+        assert!(trigger_order(true, "1910", "1900", TpSl::Tp)
+            .validate(&context)
+            .is_ok());
+        assert!(trigger_order(true, "1910", "1900", TpSl::Sl)
+            .validate(&context)
+            .is_err());
+    }
+}
@@ -1,20 +1,23 @@
 use crate::{
     info::{
-        CandlesSnapshotResponse, FundingHistoryResponse, L2SnapshotResponse, OpenOrdersResponse,
-        OrderInfo, RecentTradesResponse, UserFillsResponse, UserStateResponse,
+        CandlesSnapshotResponse, DelegationResponse, DelegatorHistoryResponse,
+        DelegatorRewardResponse, DelegatorSummaryResponse, ExtraAgentResponse,
+        FundingHistoryResponse, L2SnapshotResponse, OpenOrdersResponse, OrderInfo,
+        RecentTradesResponse, SubAccountResponse, UserFillsResponse, UserStateResponse,
+        UserTwapSliceFillsResponse,
     },
-    meta::{Meta, SpotMeta, SpotMetaAndAssetCtxs},
+    meta::{Meta, PerpDex, SpotMeta, SpotMetaAndAssetCtxs},
     prelude::*,
-    req::HttpClient,
+    req::{HttpClient, HttpConfig},
     ws::{Subscription, WsManager},
-    BaseUrl, Error, Message, OrderStatusResponse, ReferralResponse, UserFeesResponse,
-    UserFundingResponse, UserTokenBalanceResponse,
+    BaseUrl, Error, Message, OrderStatusResponse, RateLimiter, ReferralResponse, UserFeesResponse,
+    UserFundingResponse, UserTokenBalanceResponse, UserVaultEquity, VaultDetails,
 };
 
 use ethers::types::H160;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::mpsc::UnboundedSender;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -34,6 +37,11 @@ pub enum InfoRequest {
     UserState {
         user: H160,
     },
+    #[serde(rename = "clearinghouseState", skip_deserializing)]
+    UserStateForDex {
+        user: H160,
+        dex: String,
+    },
     #[serde(rename = "batchClearinghouseStates")]
     UserStates {
         users: Vec<H160>,
@@ -53,12 +61,24 @@ pub enum InfoRequest {
         oid: u64,
     },
     Meta,
+    #[serde(rename = "meta", skip_deserializing)]
+    MetaForDex {
+        dex: String,
+    },
+    PerpDexs,
     SpotMeta,
     SpotMetaAndAssetCtxs,
     AllMids,
+    #[serde(rename = "allMids", skip_deserializing)]
+    AllMidsForDex {
+        dex: String,
+    },
     UserFills {
         user: H160,
     },
+    UserTwapSliceFills {
+        user: H160,
+    },
     #[serde(rename_all = "camelCase")]
     FundingHistory {
         coin: String,
@@ -87,6 +107,50 @@ pub enum InfoRequest {
     HistoricalOrders {
         user: H160,
     },
+    SubAccounts {
+        user: H160,
+    },
+    #[serde(rename_all = "camelCase")]
+    VaultDetails {
+        vault_address: H160,
+        user: Option<H160>,
+    },
+    UserVaultEquities {
+        user: H160,
+    },
+    Delegations {
+        user: H160,
+    },
+    DelegatorSummary {
+        user: H160,
+    },
+    DelegatorHistory {
+        user: H160,
+    },
+    DelegatorRewards {
+        user: H160,
+    },
+    ExtraAgents {
+        user: H160,
+    },
+}
+
+impl InfoRequest {
+    /// Rate limit weight of the request. Responses with many items, like fills or candles, may
+    /// cost Hyperliquid additional weight that is not known in advance.
+    pub fn weight(&self) -> u32 {
+        match self {
+            InfoRequest::L2Book { .. }
+            | InfoRequest::AllMids
+            | InfoRequest::AllMidsForDex { .. }
+            | InfoRequest::UserState { .. }
+            | InfoRequest::UserStateForDex { .. }
+            | InfoRequest::UserTokenBalances { .. }
+            | InfoRequest::OrderStatus { .. } => 2,
+            InfoRequest::UserStates { users } => 2 * users.len().max(1) as u32,
+            _ => 20,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -114,26 +178,49 @@ impl InfoClient {
         reconnect: bool,
     ) -> Result<InfoClient> {
         let client = client.unwrap_or_default();
-        let base_url = base_url.unwrap_or(BaseUrl::Mainnet).get_url();
+        let base_url = base_url.unwrap_or(BaseUrl::Mainnet);
 
         Ok(InfoClient {
-            http_client: HttpClient { client, base_url },
+            http_client: HttpClient {
+                client,
+                base_url,
+                config: HttpConfig::default(),
+                rate_limiter: RateLimiter::shared(),
+            },
             ws_manager: None,
             reconnect,
         })
     }
 
+    /// Replaces the timeouts and retry policies of `/info` requests.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.http_client.config = config;
+        self
+    }
+
+    /// Replaces the budget of request weight, which by default is shared by every client of
+    /// this process.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.http_client.rate_limiter = rate_limiter;
+        self
+    }
+
+    pub(crate) fn with_http_client(http_client: HttpClient) -> InfoClient {
+        InfoClient {
+            http_client,
+            ws_manager: None,
+            reconnect: false,
+        }
+    }
+
     pub async fn subscribe(
         &mut self,
         subscription: Subscription,
         sender_channel: UnboundedSender<Message>,
     ) -> Result<u32> {
         if self.ws_manager.is_none() {
-            let ws_manager = WsManager::new(
-                format!("ws{}/ws", &self.http_client.base_url[4..]),
-                self.reconnect,
-            )
-            .await?;
+            let ws_manager =
+                WsManager::new(self.http_client.base_url.ws_url(), self.reconnect).await?;
             self.ws_manager = Some(ws_manager);
         }
 
@@ -149,11 +236,8 @@ impl InfoClient {
 
     pub async fn unsubscribe(&mut self, subscription_id: u32) -> Result<()> {
         if self.ws_manager.is_none() {
-            let ws_manager = WsManager::new(
-                format!("ws{}/ws", &self.http_client.base_url[4..]),
-                self.reconnect,
-            )
-            .await?;
+            let ws_manager =
+                WsManager::new(self.http_client.base_url.ws_url(), self.reconnect).await?;
             self.ws_manager = Some(ws_manager);
         }
 
@@ -171,7 +255,10 @@ impl InfoClient {
         let data =
             serde_json::to_string(&info_request).map_err(|e| Error::JsonParse(e.to_string()))?;
 
-        let return_data = self.http_client.post("/info", data).await?;
+        let return_data = self
+            .http_client
+            .post("/info", data, info_request.weight())
+            .await?;
         serde_json::from_str(&return_data).map_err(|e| Error::JsonParse(e.to_string()))
     }
 
@@ -185,6 +272,15 @@ impl InfoClient {
         self.send_info_request(input).await
     }
 
+    /// Positions and margin of `address` on a builder-deployed perp dex.
+    pub async fn user_state_for_dex(&self, address: H160, dex: &str) -> Result<UserStateResponse> {
+        let input = InfoRequest::UserStateForDex {
+            user: address,
+            dex: dex.to_string(),
+        };
+        self.send_info_request(input).await
+    }
+
     pub async fn user_states(&self, addresses: Vec<H160>) -> Result<Vec<UserStateResponse>> {
         let input = InfoRequest::UserStates { users: addresses };
         self.send_info_request(input).await
@@ -205,6 +301,21 @@ impl InfoClient {
         self.send_info_request(input).await
     }
 
+    /// Universe of a builder-deployed perp dex, with coins named `dex:COIN`.
+    pub async fn meta_for_dex(&self, dex: &str) -> Result<Meta> {
+        let input = InfoRequest::MetaForDex {
+            dex: dex.to_string(),
+        };
+        self.send_info_request(input).await
+    }
+
+    /// All perp dexs, indexed by their dex index. The first entry is the default dex and is
+    /// always `None`.
+    pub async fn perp_dexs(&self) -> Result<Vec<Option<PerpDex>>> {
+        let input = InfoRequest::PerpDexs;
+        self.send_info_request(input).await
+    }
+
     pub async fn spot_meta(&self) -> Result<SpotMeta> {
         let input = InfoRequest::SpotMeta;
         self.send_info_request(input).await
@@ -220,11 +331,27 @@ impl InfoClient {
         self.send_info_request(input).await
     }
 
+    /// Mid prices of a builder-deployed perp dex.
+    pub async fn all_mids_for_dex(&self, dex: &str) -> Result<HashMap<String, String>> {
+        let input = InfoRequest::AllMidsForDex {
+            dex: dex.to_string(),
+        };
+        self.send_info_request(input).await
+    }
+
     pub async fn user_fills(&self, address: H160) -> Result<Vec<UserFillsResponse>> {
         let input = InfoRequest::UserFills { user: address };
         self.send_info_request(input).await
     }
 
+    pub async fn user_twap_slice_fills(
+        &self,
+        address: H160,
+    ) -> Result<Vec<UserTwapSliceFillsResponse>> {
+        let input = InfoRequest::UserTwapSliceFills { user: address };
+        self.send_info_request(input).await
+    }
+
     pub async fn funding_history(
         &self,
         coin: String,
@@ -295,4 +422,55 @@ impl InfoClient {
         let input = InfoRequest::HistoricalOrders { user: address };
         self.send_info_request(input).await
     }
+
+    pub async fn sub_accounts(&self, address: H160) -> Result<Vec<SubAccountResponse>> {
+        let input = InfoRequest::SubAccounts { user: address };
+        // users without sub-accounts get `null`
+        let sub_accounts: Option<Vec<SubAccountResponse>> = self.send_info_request(input).await?;
+        Ok(sub_accounts.unwrap_or_default())
+    }
+
+    /// Details of a vault. When `user` is given, `follower_state` is filled with their position.
+    pub async fn vault_details(
+        &self,
+        vault_address: H160,
+        user: Option<H160>,
+    ) -> Result<VaultDetails> {
+        let input = InfoRequest::VaultDetails {
+            vault_address,
+            user,
+        };
+        self.send_info_request(input).await
+    }
+
+    pub async fn user_vault_equities(&self, address: H160) -> Result<Vec<UserVaultEquity>> {
+        let input = InfoRequest::UserVaultEquities { user: address };
+        self.send_info_request(input).await
+    }
+
+    pub async fn delegations(&self, address: H160) -> Result<Vec<DelegationResponse>> {
+        let input = InfoRequest::Delegations { user: address };
+        self.send_info_request(input).await
+    }
+
+    pub async fn delegator_summary(&self, address: H160) -> Result<DelegatorSummaryResponse> {
+        let input = InfoRequest::DelegatorSummary { user: address };
+        self.send_info_request(input).await
+    }
+
+    pub async fn delegator_history(&self, address: H160) -> Result<Vec<DelegatorHistoryResponse>> {
+        let input = InfoRequest::DelegatorHistory { user: address };
+        self.send_info_request(input).await
+    }
+
+    pub async fn delegator_rewards(&self, address: H160) -> Result<Vec<DelegatorRewardResponse>> {
+        let input = InfoRequest::DelegatorRewards { user: address };
+        self.send_info_request(input).await
+    }
+
+    /// Agents approved by `address` in addition to its unnamed agent.
+    pub async fn extra_agents(&self, address: H160) -> Result<Vec<ExtraAgentResponse>> {
+        let input = InfoRequest::ExtraAgents { user: address };
+        self.send_info_request(input).await
+    }
 }
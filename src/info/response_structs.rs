@@ -1,7 +1,9 @@
 use crate::{
     info::{AssetPosition, Level, MarginSummary},
-    DailyUserVlm, Delta, FeeSchedule, OrderInfo, Referrer, ReferrerState, UserTokenBalance,
+    DailyUserVlm, Decimal, Delta, FeeSchedule, FillDirection, OrderInfo, Referrer, ReferrerState,
+    Side, UserTokenBalance,
 };
+use ethers::types::H160;
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
@@ -18,6 +20,16 @@ pub struct UserTokenBalanceResponse {
     pub balances: Vec<UserTokenBalance>,
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountResponse {
+    pub name: String,
+    pub sub_account_user: H160,
+    pub master: H160,
+    pub clearinghouse_state: UserStateResponse,
+    pub spot_state: UserTokenBalanceResponse,
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UserFeesResponse {
@@ -32,28 +44,35 @@ pub struct UserFeesResponse {
 #[serde(rename_all = "camelCase")]
 pub struct OpenOrdersResponse {
     pub coin: String,
-    pub limit_px: String,
+    pub limit_px: Decimal,
     pub oid: u64,
-    pub side: String,
-    pub sz: String,
+    pub side: Side,
+    pub sz: Decimal,
     pub timestamp: u64,
 }
 
 #[derive(serde::Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UserFillsResponse {
-    pub closed_pnl: String,
+    pub closed_pnl: Decimal,
     pub coin: String,
     pub crossed: bool,
-    pub dir: String,
+    pub dir: FillDirection,
     pub hash: String,
     pub oid: u64,
-    pub px: String,
-    pub side: String,
-    pub start_position: String,
-    pub sz: String,
+    pub px: Decimal,
+    pub side: Side,
+    pub start_position: Decimal,
+    pub sz: Decimal,
     pub time: u64,
-    pub fee: String,
+    pub fee: Decimal,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTwapSliceFillsResponse {
+    pub fill: UserFillsResponse,
+    pub twap_id: u64,
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -84,9 +103,9 @@ pub struct L2SnapshotResponse {
 #[serde(rename_all = "camelCase")]
 pub struct RecentTradesResponse {
     pub coin: String,
-    pub side: String,
-    pub px: String,
-    pub sz: String,
+    pub side: Side,
+    pub px: Decimal,
+    pub sz: Decimal,
     pub time: u64,
     pub hash: String,
 }
@@ -132,3 +151,63 @@ pub struct ReferralResponse {
     pub claimed_rewards: String,
     pub referrer_state: ReferrerState,
 }
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationResponse {
+    pub validator: H160,
+    pub amount: Decimal,
+    pub locked_until_timestamp: u64,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorSummaryResponse {
+    pub delegated: Decimal,
+    pub undelegated: Decimal,
+    pub total_pending_withdrawal: Decimal,
+    pub n_pending_withdrawals: u64,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorHistoryResponse {
+    pub time: u64,
+    pub hash: String,
+    pub delta: DelegatorDelta,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum DelegatorDelta {
+    #[serde(rename_all = "camelCase")]
+    Delegate {
+        validator: H160,
+        amount: Decimal,
+        is_undelegate: bool,
+    },
+    CDeposit {
+        amount: Decimal,
+    },
+    Withdrawal {
+        amount: Decimal,
+        phase: String,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorRewardResponse {
+    pub time: u64,
+    /// "delegation" or "commission"
+    pub source: String,
+    pub total_amount: Decimal,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraAgentResponse {
+    pub address: H160,
+    pub name: String,
+    pub valid_until: u64,
+}
@@ -1,5 +1,87 @@
+use crate::Decimal;
 use ethers::types::H160;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    #[serde(rename = "B")]
+    Bid,
+    #[serde(rename = "A")]
+    Ask,
+}
+
+impl Side {
+    pub fn is_buy(&self) -> bool {
+        *self == Side::Bid
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Bid => f.write_str("B"),
+            Side::Ask => f.write_str("A"),
+        }
+    }
+}
+
+/// The `dir` of a fill. Directions without a dedicated variant (liquidations, settlements,
+/// ...) are kept verbatim in `Other`.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum FillDirection {
+    OpenLong,
+    OpenShort,
+    CloseLong,
+    CloseShort,
+    /// Spot buy
+    Buy,
+    /// Spot sell
+    Sell,
+    LongToShort,
+    ShortToLong,
+    Other(String),
+}
+
+impl From<String> for FillDirection {
+    fn from(dir: String) -> Self {
+        match dir.as_str() {
+            "Open Long" => FillDirection::OpenLong,
+            "Open Short" => FillDirection::OpenShort,
+            "Close Long" => FillDirection::CloseLong,
+            "Close Short" => FillDirection::CloseShort,
+            "Buy" => FillDirection::Buy,
+            "Sell" => FillDirection::Sell,
+            "Long > Short" => FillDirection::LongToShort,
+            "Short > Long" => FillDirection::ShortToLong,
+            _ => FillDirection::Other(dir),
+        }
+    }
+}
+
+impl From<FillDirection> for String {
+    fn from(dir: FillDirection) -> Self {
+        dir.to_string()
+    }
+}
+
+impl fmt::Display for FillDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dir = match self {
+            FillDirection::OpenLong => "Open Long",
+            FillDirection::OpenShort => "Open Short",
+            FillDirection::CloseLong => "Close Long",
+            FillDirection::CloseShort => "Close Short",
+            FillDirection::Buy => "Buy",
+            FillDirection::Sell => "Sell",
+            FillDirection::LongToShort => "Long > Short",
+            FillDirection::ShortToLong => "Short > Long",
+            FillDirection::Other(dir) => dir,
+        };
+        f.write_str(dir)
+    }
+}
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -22,14 +104,14 @@ pub struct CumulativeFunding {
 #[serde(rename_all = "camelCase")]
 pub struct PositionData {
     pub coin: String,
-    pub entry_px: Option<String>,
+    pub entry_px: Option<Decimal>,
     pub leverage: Leverage,
-    pub liquidation_px: Option<String>,
-    pub margin_used: String,
-    pub position_value: String,
-    pub return_on_equity: String,
-    pub szi: String,
-    pub unrealized_pnl: String,
+    pub liquidation_px: Option<Decimal>,
+    pub margin_used: Decimal,
+    pub position_value: Decimal,
+    pub return_on_equity: Decimal,
+    pub szi: Decimal,
+    pub unrealized_pnl: Decimal,
     pub max_leverage: u32,
     pub cum_funding: CumulativeFunding,
 }
@@ -54,8 +136,8 @@ pub struct MarginSummary {
 #[serde(rename_all = "camelCase")]
 pub struct Level {
     pub n: u64,
-    pub px: String,
-    pub sz: String,
+    pub px: Decimal,
+    pub sz: Decimal,
 }
 
 #[derive(Deserialize, Debug)]
@@ -127,18 +209,18 @@ pub struct OrderInfo {
 #[serde(rename_all = "camelCase")]
 pub struct BasicOrderInfo {
     pub coin: String,
-    pub side: String,
-    pub limit_px: String,
-    pub sz: String,
+    pub side: Side,
+    pub limit_px: Decimal,
+    pub sz: Decimal,
     pub oid: u64,
     pub timestamp: u64,
     pub trigger_condition: String,
     pub is_trigger: bool,
-    pub trigger_px: String,
+    pub trigger_px: Decimal,
     pub is_position_tpsl: bool,
     pub reduce_only: bool,
     pub order_type: String,
-    pub orig_sz: String,
+    pub orig_sz: Decimal,
     pub tif: String,
     pub cloid: Option<String>,
 }
@@ -162,3 +244,27 @@ pub struct ReferrerState {
 pub struct ReferrerData {
     pub required: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn side_and_fill_direction_serde() {
+        let side: Side = serde_json::from_str("\"B\"").unwrap();
+        assert_eq!(side, Side::Bid);
+        assert_eq!(serde_json::to_string(&Side::Ask).unwrap(), "\"A\"");
+
+        let dir: FillDirection = serde_json::from_str("\"Long > Short\"").unwrap();
+        assert_eq!(dir, FillDirection::LongToShort);
+        let dir: FillDirection = serde_json::from_str("\"Liquidated Isolated Long\"").unwrap();
+        assert_eq!(
+            dir,
+            FillDirection::Other("Liquidated Isolated Long".to_string())
+        );
+        assert_eq!(
+            serde_json::to_string(&dir).unwrap(),
+            "\"Liquidated Isolated Long\""
+        );
+    }
+}
@@ -1,5 +1,6 @@
 #![deny(unreachable_pub)]
 mod consts;
+mod decimal;
 mod errors;
 mod exchange;
 mod helpers;
@@ -8,14 +9,25 @@ mod market_maker;
 mod meta;
 mod prelude;
 mod proxy_digest;
+mod rate_limit;
 mod req;
 mod signature;
+mod wallet;
 mod ws;
 pub use consts::{EPSILON, LOCAL_API_URL, MAINNET_API_URL, TESTNET_API_URL};
+pub use decimal::{Decimal, RoundingMode};
 pub use errors::Error;
 pub use exchange::*;
 pub use helpers::{bps_diff, truncate_float, BaseUrl};
 pub use info::{info_client::*, *};
 pub use market_maker::{MarketMaker, MarketMakerInput, MarketMakerRestingOrder};
-pub use meta::{AssetMeta, Meta};
+pub use meta::{AssetMeta, Meta, PerpDex, SpotAssetMeta, SpotMeta, TokenInfo};
+pub use rate_limit::{
+    exchange_action_weight, RateLimitMode, RateLimiter, DEFAULT_WEIGHT_PER_MINUTE,
+};
+pub use req::{EndpointConfig, HttpConfig, RetryOn, RetryPolicy};
+pub use signature::{
+    recover_hash_signer, recover_l1_action_signer, recover_typed_data_signer, HyperliquidSigner,
+};
+pub use wallet::{load_wallet_from_args, WalletSource, KEYSTORE_PASSWORD_ENV, WALLET_FLAG};
 pub use ws::*;
@@ -8,8 +8,8 @@ use tokio::sync::mpsc::unbounded_channel;
 
 use crate::{
     bps_diff, truncate_float, BaseUrl, ClientCancelRequest, ClientLimit, ClientOrder,
-    ClientOrderRequest, ExchangeClient, ExchangeDataStatus, ExchangeResponseStatus, InfoClient,
-    Message, Subscription, UserData, EPSILON,
+    ClientOrderRequest, Decimal, ExchangeClient, ExchangeDataStatus, ExchangeResponseStatus,
+    InfoClient, Message, Subscription, Tif, UserData, EPSILON,
 };
 #[derive(Debug)]
 pub struct MarketMakerRestingOrder {
@@ -127,9 +127,9 @@ impl MarketMaker {
                     let user_events = user_events.data;
                     if let UserData::Fills(fills) = user_events {
                         for fill in fills {
-                            let amount: f64 = fill.sz.parse().unwrap();
+                            let amount = fill.sz.to_f64();
                             // Update our resting positions whenever we see a fill
-                            if fill.side.eq("B") {
+                            if fill.side.is_buy() {
                                 self.cur_position += amount;
                                 self.lower_resting.position -= amount;
                                 info!("Fill: bought {amount} {}", self.asset.clone());
@@ -191,6 +191,14 @@ impl MarketMaker {
         price: f64,
         is_buy: bool,
     ) -> (f64, u64) {
+        // Strip float noise; sizes keep the 8 decimals allowed on the wire
+        let (limit_px, sz) = match (Decimal::try_from(price), Decimal::try_from(amount)) {
+            (Ok(limit_px), Ok(sz)) => (limit_px.round_dp(self.decimals), sz.round_dp(8)),
+            _ => {
+                error!("Invalid price {price} or amount {amount} for order");
+                return (0.0, 0);
+            }
+        };
         let order = self
             .exchange_client
             .order(
@@ -198,12 +206,10 @@ impl MarketMaker {
                     asset,
                     is_buy,
                     reduce_only: false,
-                    limit_px: price,
-                    sz: amount,
+                    limit_px,
+                    sz,
                     cloid: None,
-                    order_type: ClientOrder::Limit(ClientLimit {
-                        tif: "Gtc".to_string(),
-                    }),
+                    order_type: ClientOrder::Limit(ClientLimit { tif: Tif::Gtc }),
                 },
                 None,
             )
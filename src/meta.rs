@@ -1,13 +1,64 @@
 use std::collections::HashMap;
 
-use ethers::abi::ethereum_types::H128;
+use ethers::{abi::ethereum_types::H128, types::H160};
 use serde::Deserialize;
 
+/// The perp dex of a `dex:COIN` name, empty for the default dex and for spot.
+pub(crate) fn coin_perp_dex(coin: &str) -> &str {
+    coin.split_once(':').map_or("", |(dex, _)| dex)
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct Meta {
     pub universe: Vec<AssetMeta>,
 }
 
+impl Meta {
+    /// Offset of the asset ids of the perp dex at `dex_index` in `perpDexs`. The default dex has
+    /// index 0 and no offset.
+    pub fn perp_dex_asset_offset(dex_index: usize) -> u32 {
+        if dex_index == 0 {
+            0
+        } else {
+            100000 + dex_index as u32 * 10000
+        }
+    }
+
+    /// Maps the coins of this perp dex universe, named `dex:COIN`, to their asset ids.
+    pub fn add_perp_dex_to_index_map(
+        &self,
+        dex: &str,
+        dex_index: usize,
+        mut coin_to_asset: HashMap<String, u32>,
+    ) -> HashMap<String, u32> {
+        let offset = Self::perp_dex_asset_offset(dex_index);
+        for (asset_ind, asset) in self.universe.iter().enumerate() {
+            coin_to_asset.insert(perp_dex_coin(dex, &asset.name), offset + asset_ind as u32);
+        }
+        coin_to_asset
+    }
+}
+
+/// Name of `coin` on a builder-deployed perp dex. The universe returned for a dex already uses
+/// the `dex:COIN` form, which is kept as is.
+pub(crate) fn perp_dex_coin(dex: &str, coin: &str) -> String {
+    if dex.is_empty() || coin.starts_with(&format!("{dex}:")) {
+        coin.to_string()
+    } else {
+        format!("{dex}:{coin}")
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpDex {
+    pub name: String,
+    pub full_name: String,
+    pub deployer: H160,
+    pub oracle_updater: Option<H160>,
+    pub fee_recipient: Option<H160>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct SpotMeta {
     pub universe: Vec<SpotAssetMeta>,
@@ -89,3 +140,26 @@ pub struct TokenInfo {
     pub token_id: H128,
     pub is_canonical: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perp_dex_asset_ids() {
+        let meta: Meta = serde_json::from_str(
+            r#"{"universe": [{"name": "test:ABC", "szDecimals": 2}, {"name": "DEF", "szDecimals": 0}]}"#,
+        )
+        .unwrap();
+
+        let coin_to_asset = meta.add_perp_dex_to_index_map("test", 2, HashMap::new());
+        assert_eq!(coin_to_asset["test:ABC"], 120000);
+        assert_eq!(coin_to_asset["test:DEF"], 120001);
+        assert_eq!(Meta::perp_dex_asset_offset(0), 0);
+        assert_eq!(Meta::perp_dex_asset_offset(1), 110000);
+
+        assert_eq!(coin_perp_dex("test:ABC"), "test");
+        assert_eq!(coin_perp_dex("ETH"), "");
+        assert_eq!(coin_perp_dex("PURR/USDC"), "");
+    }
+}
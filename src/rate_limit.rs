@@ -0,0 +1,207 @@
+use crate::{prelude::*, Error};
+use lazy_static::lazy_static;
+use serde_json::Value;
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Weight Hyperliquid allows per IP address and minute, summed over `/info` and `/exchange`.
+pub const DEFAULT_WEIGHT_PER_MINUTE: u32 = 1200;
+
+/// What [`RateLimiter::acquire`] does when the budget is exhausted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitMode {
+    /// Return [`Error::RateLimited`] without sending the request.
+    #[default]
+    Fail,
+    /// Sleep until the request fits into the budget.
+    Wait,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    weight: f64,
+    refilled_at: Instant,
+}
+
+/// Token bucket holding the request weight that may still be spent. It refills continuously up
+/// to the weight allowed per minute.
+#[derive(Debug)]
+pub struct RateLimiter {
+    weight_per_minute: u32,
+    mode: Mutex<RateLimitMode>,
+    bucket: Mutex<Bucket>,
+}
+
+lazy_static! {
+    static ref SHARED_LIMITER: Arc<RateLimiter> =
+        Arc::new(RateLimiter::new(DEFAULT_WEIGHT_PER_MINUTE));
+}
+
+impl RateLimiter {
+    pub fn new(weight_per_minute: u32) -> RateLimiter {
+        RateLimiter {
+            weight_per_minute,
+            mode: Mutex::new(RateLimitMode::Fail),
+            bucket: Mutex::new(Bucket {
+                weight: weight_per_minute as f64,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn with_mode(self, mode: RateLimitMode) -> Self {
+        self.set_mode(mode);
+        self
+    }
+
+    /// The limiter shared by every client of this process, as the limits apply per IP address.
+    /// It fails requests over the budget until switched with [`Self::set_mode`].
+    pub fn shared() -> Arc<RateLimiter> {
+        SHARED_LIMITER.clone()
+    }
+
+    pub fn mode(&self) -> RateLimitMode {
+        *self.mode.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Changes the mode of a limiter that may already be shared, e.g.
+    /// `RateLimiter::shared().set_mode(RateLimitMode::Wait)`.
+    pub fn set_mode(&self, mode: RateLimitMode) {
+        *self.mode.lock().unwrap_or_else(PoisonError::into_inner) = mode;
+    }
+
+    pub fn weight_per_minute(&self) -> u32 {
+        self.weight_per_minute
+    }
+
+    /// Weight that can be spent right now.
+    pub fn remaining(&self) -> u32 {
+        let mut bucket = self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+        self.refill(&mut bucket);
+        bucket.weight as u32
+    }
+
+    /// Spends `weight`, or returns how long to wait until it is available.
+    pub fn try_acquire(&self, weight: u32) -> std::result::Result<(), Duration> {
+        // a request heavier than the whole budget is let through once the bucket is full
+        let weight = weight.min(self.weight_per_minute) as f64;
+        let mut bucket = self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+        self.refill(&mut bucket);
+        if bucket.weight >= weight {
+            bucket.weight -= weight;
+            return Ok(());
+        }
+        let missing = weight - bucket.weight;
+        Err(Duration::from_secs_f64(
+            missing * 60.0 / self.weight_per_minute as f64,
+        ))
+    }
+
+    /// Spends `weight`, waiting for it or failing according to the mode.
+    pub async fn acquire(&self, weight: u32) -> Result<()> {
+        loop {
+            match self.try_acquire(weight) {
+                Ok(()) => return Ok(()),
+                Err(retry_after) if self.mode() == RateLimitMode::Wait => {
+                    tokio::time::sleep(retry_after).await;
+                }
+                Err(retry_after) => {
+                    return Err(Error::RateLimited {
+                        weight,
+                        retry_after,
+                    })
+                }
+            }
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        let capacity = self.weight_per_minute as f64;
+        bucket.weight = (bucket.weight + elapsed * capacity / 60.0).min(capacity);
+        bucket.refilled_at = now;
+    }
+}
+
+/// Weight of an `/exchange` action: 1, plus 1 for every 40 orders or cancels in a batch.
+pub fn exchange_action_weight(action: &Value) -> u32 {
+    let batch_len = match action["type"].as_str() {
+        Some("order") => action["orders"].as_array().map_or(0, Vec::len),
+        Some("cancel") | Some("cancelByCloid") => action["cancels"].as_array().map_or(0, Vec::len),
+        Some("batchModify") => action["modifies"].as_array().map_or(0, Vec::len),
+        Some("multiSig") => return exchange_action_weight(&action["payload"]["action"]),
+        _ => 0,
+    };
+    1 + batch_len as u32 / 40
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InfoRequest;
+    use ethers::types::H160;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_token_bucket() {
+        let limiter = RateLimiter::new(60);
+        assert_eq!(limiter.try_acquire(50), Ok(()));
+        assert_eq!(limiter.remaining(), 10);
+
+        let retry_after = limiter.try_acquire(20).unwrap_err();
+        // 10 missing weight refill at 1 per second
+        assert!(retry_after > Duration::from_secs(9) && retry_after <= Duration::from_secs(10));
+        assert_eq!(limiter.remaining(), 10);
+        assert!(matches!(
+            limiter.acquire(20).await,
+            Err(Error::RateLimited { weight: 20, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_mode() -> Result<()> {
+        // refills 1 weight every 10ms
+        let limiter = RateLimiter::new(6000);
+        limiter.acquire(6000).await?;
+        assert!(matches!(
+            limiter.acquire(3).await,
+            Err(Error::RateLimited { .. })
+        ));
+
+        limiter.set_mode(RateLimitMode::Wait);
+        assert_eq!(limiter.mode(), RateLimitMode::Wait);
+        let start = Instant::now();
+        limiter.acquire(3).await?;
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        Ok(())
+    }
+
+    #[test]
+    fn test_request_weights() {
+        assert_eq!(InfoRequest::AllMids.weight(), 2);
+        assert_eq!(InfoRequest::Meta.weight(), 20);
+        let users = vec![H160::zero(); 3];
+        assert_eq!(InfoRequest::UserStates { users }.weight(), 6);
+
+        let orders = vec![json!({}); 85];
+        assert_eq!(exchange_action_weight(&json!({"type": "usdSend"})), 1);
+        assert_eq!(
+            exchange_action_weight(&json!({"type": "order", "orders": orders[..39]})),
+            1
+        );
+        assert_eq!(
+            exchange_action_weight(&json!({"type": "cancel", "cancels": orders})),
+            3
+        );
+        assert_eq!(
+            exchange_action_weight(&json!({
+                "type": "multiSig",
+                "payload": {"action": {"type": "batchModify", "modifies": orders[..40]}}
+            })),
+            2
+        );
+    }
+}
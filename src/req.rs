@@ -1,6 +1,9 @@
-use crate::{prelude::*, BaseUrl, Error};
+use crate::{prelude::*, BaseUrl, Error, RateLimiter};
+use log::warn;
+use rand::{thread_rng, Rng};
 use reqwest::{Client, Response};
 use serde::Deserialize;
+use std::{sync::Arc, time::Duration};
 
 #[derive(Deserialize, Debug)]
 struct ErrorData {
@@ -9,18 +12,137 @@ struct ErrorData {
     msg: String,
 }
 
-#[derive(Debug)]
+/// Which failed requests are retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryOn {
+    /// Connect failures, timeouts, 429 and 5xx responses. Suitable for `/info`, which has no
+    /// side effects.
+    AnyFailure,
+    /// Only requests that never reached the server. The retry replays the same signed payload,
+    /// so an action is never executed twice.
+    ConnectFailure,
+}
+
+/// Retries with jittered exponential backoff: the n-th retry waits between half and all of
+/// `initial_backoff * 2^n`, capped at `max_backoff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub retry_on: RetryOn,
+}
+
+impl RetryPolicy {
+    pub const NONE: RetryPolicy = RetryPolicy {
+        max_retries: 0,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+        retry_on: RetryOn::ConnectFailure,
+    };
+
+    fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff);
+        backoff.mul_f64(thread_rng().gen_range(0.5..=1.0))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointConfig {
+    /// `None` waits forever.
+    pub timeout: Option<Duration>,
+    pub retry: RetryPolicy,
+}
+
+/// Timeouts and retries of the `/info` and `/exchange` endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpConfig {
+    pub info: EndpointConfig,
+    pub exchange: EndpointConfig,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            info: EndpointConfig {
+                timeout: Some(Duration::from_secs(10)),
+                retry: RetryPolicy {
+                    max_retries: 3,
+                    initial_backoff: Duration::from_millis(250),
+                    max_backoff: Duration::from_secs(5),
+                    retry_on: RetryOn::AnyFailure,
+                },
+            },
+            exchange: EndpointConfig {
+                timeout: Some(Duration::from_secs(10)),
+                retry: RetryPolicy {
+                    max_retries: 2,
+                    initial_backoff: Duration::from_millis(100),
+                    max_backoff: Duration::from_secs(1),
+                    retry_on: RetryOn::ConnectFailure,
+                },
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct HttpClient {
     pub client: Client,
-    pub base_url: String,
+    pub base_url: BaseUrl,
+    pub config: HttpConfig,
+    /// Budget of request weight, shared with the other clients of this process by default.
+    pub rate_limiter: Arc<RateLimiter>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FailureKind {
+    Connect,
+    Timeout,
+    RateLimited,
+    Server,
+    Other,
+}
+
+impl FailureKind {
+    fn of(err: &Error) -> FailureKind {
+        match err {
+            Error::Connect(_) => FailureKind::Connect,
+            Error::Timeout(_) => FailureKind::Timeout,
+            Error::ClientRequest {
+                status_code: 429, ..
+            } => FailureKind::RateLimited,
+            Error::ServerRequest { .. } => FailureKind::Server,
+            _ => FailureKind::Other,
+        }
+    }
+}
+
+impl RetryOn {
+    fn allows(self, kind: FailureKind) -> bool {
+        match self {
+            RetryOn::AnyFailure => kind != FailureKind::Other,
+            RetryOn::ConnectFailure => kind == FailureKind::Connect,
+        }
+    }
+}
+
+fn transport_error(err: reqwest::Error) -> Error {
+    if err.is_connect() {
+        Error::Connect(err.to_string())
+    } else if err.is_timeout() {
+        Error::Timeout(err.to_string())
+    } else {
+        Error::GenericRequest(err.to_string())
+    }
 }
 
 async fn parse_response(response: Response) -> Result<String> {
     let status_code = response.status().as_u16();
-    let text = response
-        .text()
-        .await
-        .map_err(|e| Error::GenericRequest(e.to_string()))?;
+    let text = response.text().await.map_err(transport_error)?;
 
     if status_code < 400 {
         return Ok(text);
@@ -51,24 +173,176 @@ async fn parse_response(response: Response) -> Result<String> {
 }
 
 impl HttpClient {
-    pub async fn post(&self, url_path: &'static str, data: String) -> Result<String> {
-        let full_url = format!("{}{url_path}", self.base_url);
-        let request = self
+    /// Posts `data`, spending `weight` of the rate limit budget on every attempt.
+    pub async fn post(&self, url_path: &'static str, data: String, weight: u32) -> Result<String> {
+        let endpoint = if url_path == "/exchange" {
+            self.config.exchange
+        } else {
+            self.config.info
+        };
+
+        let mut retry = 0;
+        loop {
+            self.rate_limiter.acquire(weight).await?;
+            match self.send(url_path, endpoint.timeout, data.clone()).await {
+                Err(err)
+                    if retry < endpoint.retry.max_retries
+                        && endpoint.retry.retry_on.allows(FailureKind::of(&err)) =>
+                {
+                    let backoff = endpoint.retry.backoff(retry);
+                    warn!("Retrying {url_path} in {backoff:?} after {err}");
+                    tokio::time::sleep(backoff).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        url_path: &'static str,
+        timeout: Option<Duration>,
+        data: String,
+    ) -> Result<String> {
+        let full_url = format!("{}{url_path}", self.base_url.get_url());
+        let mut request = self
             .client
             .post(full_url)
             .header("Content-Type", "application/json")
-            .body(data)
-            .build()
-            .map_err(|e| Error::GenericRequest(e.to_string()))?;
-        let result = self
-            .client
-            .execute(request)
-            .await
-            .map_err(|e| Error::GenericRequest(e.to_string()))?;
-        parse_response(result).await
+            .body(data);
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request.send().await.map_err(transport_error)?;
+        parse_response(response).await
     }
 
     pub fn is_mainnet(&self) -> bool {
-        self.base_url == BaseUrl::Mainnet.get_url()
+        self.base_url.is_mainnet()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    fn io_error(err: std::io::Error) -> Error {
+        Error::GenericRequest(err.to_string())
+    }
+
+    fn local_url(listener: &TcpListener) -> Result<BaseUrl> {
+        let addr = listener.local_addr().map_err(io_error)?;
+        Ok(BaseUrl::Custom {
+            http: format!("http://{addr}"),
+            ws: format!("ws://{addr}/ws"),
+            is_mainnet: false,
+        })
+    }
+
+    /// Answers every request with 503 until `failures` requests were made, then with 200.
+    async fn flaky_server(failures: usize) -> Result<(BaseUrl, Arc<AtomicUsize>)> {
+        let listener = TcpListener::bind("127.0.0.1:0").await.map_err(io_error)?;
+        let base_url = local_url(&listener)?;
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf).await;
+                let (status, body) = if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                    ("503 Service Unavailable", "busy")
+                } else {
+                    ("200 OK", "{}")
+                };
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        Ok((base_url, requests))
+    }
+
+    fn http_client(base_url: BaseUrl) -> HttpClient {
+        let mut config = HttpConfig::default();
+        config.info.retry.initial_backoff = Duration::from_millis(1);
+        config.exchange.retry.initial_backoff = Duration::from_millis(1);
+        HttpClient {
+            client: Client::new(),
+            base_url,
+            config,
+            rate_limiter: Arc::new(RateLimiter::new(1200)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_info_retries_server_errors() -> Result<()> {
+        let (base_url, requests) = flaky_server(2).await?;
+        let output = http_client(base_url)
+            .post("/info", "{}".to_string(), 1)
+            .await?;
+        assert_eq!(output, "{}");
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_exchange_does_not_retry_server_errors() -> Result<()> {
+        let (base_url, requests) = flaky_server(1).await?;
+        let output = http_client(base_url)
+            .post("/exchange", "{}".to_string(), 1)
+            .await;
+        assert!(matches!(
+            output,
+            Err(Error::ServerRequest {
+                status_code: 503,
+                ..
+            })
+        ));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transport_errors() -> Result<()> {
+        // nothing listens once the listener is dropped
+        let listener = TcpListener::bind("127.0.0.1:0").await.map_err(io_error)?;
+        let closed = local_url(&listener)?;
+        drop(listener);
+        let output = http_client(closed)
+            .post("/exchange", "{}".to_string(), 1)
+            .await;
+        assert!(matches!(output, Err(Error::Connect(_))));
+
+        // accepts connections but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").await.map_err(io_error)?;
+        let silent = local_url(&listener)?;
+        let mut client = http_client(silent);
+        client.config.exchange.timeout = Some(Duration::from_millis(50));
+        let output = client.post("/exchange", "{}".to_string(), 1).await;
+        assert!(matches!(output, Err(Error::Timeout(_))));
+        drop(listener);
+        Ok(())
+    }
+
+    #[test]
+    fn test_base_urls() {
+        assert_eq!(BaseUrl::Mainnet.ws_url(), "wss://api.hyperliquid.xyz/ws");
+        assert_eq!(BaseUrl::Localhost.ws_url(), "ws://localhost:3001/ws");
+        let proxy = BaseUrl::Custom {
+            http: "https://proxy.example.com/hl/".to_string(),
+            ws: "wss://proxy.example.com/hl-ws".to_string(),
+            is_mainnet: true,
+        };
+        assert_eq!(proxy.get_url(), "https://proxy.example.com/hl");
+        assert_eq!(proxy.ws_url(), "wss://proxy.example.com/hl-ws");
+        assert!(proxy.is_mainnet() && !BaseUrl::Testnet.is_mainnet());
     }
 }
@@ -29,7 +29,7 @@ pub(crate) fn sign_typed_data<T: Eip712>(payload: &T, wallet: &LocalWallet) -> R
     sign_hash(H256::from(encoded), wallet)
 }
 
-fn sign_hash(hash: H256, wallet: &LocalWallet) -> Result<Signature> {
+pub(super) fn sign_hash(hash: H256, wallet: &LocalWallet) -> Result<Signature> {
     let (sig, rec_id) = wallet
         .signer()
         .sign_digest_recoverable(Sha256Proxy::from(hash))
@@ -48,7 +48,8 @@ fn sign_hash(hash: H256, wallet: &LocalWallet) -> Result<Signature> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{UsdSend, Withdraw3};
+    use crate::{ApproveAgent, CDeposit, CWithdraw, TokenDelegate, UsdSend, Withdraw3};
+    use ethers::types::H160;
     use std::str::FromStr;
 
     fn get_wallet() -> Result<LocalWallet> {
@@ -117,4 +118,62 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_sign_staking_actions() -> Result<()> {
+        let wallet = get_wallet()?;
+
+        let c_deposit = CDeposit {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            wei: 100000000,
+            nonce: 1690393044548,
+        };
+        assert_eq!(sign_typed_data(&c_deposit, &wallet)?.to_string(), "236016598893e096f334bc60e1e70ad73558b073f439852a703617725fecf42535d704febfb519dd41f47d889899504fb10dee1dc1183131c936b5546159994b1b");
+
+        let c_withdraw = CWithdraw {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            wei: 100000000,
+            nonce: 1690393044548,
+        };
+        assert_eq!(sign_typed_data(&c_withdraw, &wallet)?.to_string(), "9cb3f555c1c4b9861fe4ee3a294add9cf9d9c362ef02e2e54c1735bf21d65574758a01f015666b7353d87f74e910a3080dabf31ee9b80dd4347b34f37f7c1c7f1b");
+
+        let token_delegate = TokenDelegate {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            validator: H160::from_str("0x0D1d9635D0640821d15e323ac8AdADfA9c111414")
+                .map_err(|e| Error::GenericParse(e.to_string()))?,
+            wei: 100000000,
+            is_undelegate: false,
+            nonce: 1690393044548,
+        };
+        assert_eq!(
+            sign_typed_data(&token_delegate, &wallet)?.to_string(),
+            "46458242f6b0e1600b3ad0f90ed1ec7711544741d1140601570866661d03caa5295fc61d2be37dcd1185cb441bf42779ca8a294d2b015a290c043dc6aee2497c1c"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sign_approve_agent_action() -> Result<()> {
+        let wallet = get_wallet()?;
+
+        let mut approve_agent = ApproveAgent {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            agent_address: H160::from_str("0x0D1d9635D0640821d15e323ac8AdADfA9c111414")
+                .map_err(|e| Error::GenericParse(e.to_string()))?,
+            agent_name: None,
+            nonce: 1690393044548,
+        };
+        let json =
+            serde_json::to_value(&approve_agent).map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert!(json.get("agentName").is_none());
+        assert_eq!(sign_typed_data(&approve_agent, &wallet)?.to_string(), "ae44aafd27bdf2a8d2f002120baf1dd390fd7c6edf1b312f288f8d65787d27ff6a64c2bd35ae76da6ac60b8c5e8ee3ca19da6e5997ce7a64e97c36572266ef8e1c");
+
+        approve_agent.agent_name = Some("bot".to_string());
+        assert_eq!(sign_typed_data(&approve_agent, &wallet)?.to_string(), "9592d86df152f629f552079da5002114e9cef0a3bcb3d1aba4dcd616be69c17f625b3a8a4066f89e38fa661e821a638d1e48082bea2cec18fc5b9f45b30cb1521c");
+        Ok(())
+    }
 }
@@ -1,4 +1,9 @@
 pub(crate) mod agent;
 mod create_signature;
+mod recover;
+mod signer;
 
+#[cfg(test)]
 pub(crate) use create_signature::{sign_l1_action, sign_typed_data};
+pub use recover::{recover_hash_signer, recover_l1_action_signer, recover_typed_data_signer};
+pub use signer::HyperliquidSigner;
@@ -0,0 +1,64 @@
+use crate::{prelude::*, signature::agent::l1, Error};
+use ethers::types::{transaction::eip712::Eip712, Signature, H160, H256};
+
+/// Recovers the address that signed a 32 byte EIP-712 digest.
+pub fn recover_hash_signer(hash: H256, signature: &Signature) -> Result<H160> {
+    signature
+        .recover(hash)
+        .map_err(|e| Error::SignatureFailure(e.to_string()))
+}
+
+/// Recovers the signer of a user-signed action such as `UsdSend` or `Withdraw3`.
+pub fn recover_typed_data_signer<T: Eip712>(payload: &T, signature: &Signature) -> Result<H160> {
+    let encoded = payload
+        .encode_eip712()
+        .map_err(|e| Error::Eip712(e.to_string()))?;
+    recover_hash_signer(H256::from(encoded), signature)
+}
+
+/// Recovers the signer of an L1 action from its connection id.
+pub fn recover_l1_action_signer(
+    connection_id: H256,
+    signature: &Signature,
+    is_mainnet: bool,
+) -> Result<H160> {
+    let source = if is_mainnet { "a" } else { "b" }.to_string();
+    recover_typed_data_signer(
+        &l1::Agent {
+            source,
+            connection_id,
+        },
+        signature,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::{LocalWallet, Signer};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_recover_l1_action_signer() -> Result<()> {
+        let wallet: LocalWallet =
+            "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+                .parse()
+                .map_err(|e: ethers::signers::WalletError| Error::Wallet(e.to_string()))?;
+        let connection_id =
+            H256::from_str("0xde6c4037798a4434ca03cd05f00e3b803126221375cd1e7eaaaf041768be06eb")
+                .map_err(|e| Error::GenericParse(e.to_string()))?;
+        let signature = Signature::from_str("fa8a41f6a3fa728206df80801a83bcbfbab08649cd34d9c0bfba7c7b2f99340f53a00226604567b98a1492803190d65a201d6805e5831b7044f17fd530aec7841c")
+            .map_err(|e| Error::GenericParse(e.to_string()))?;
+
+        assert_eq!(
+            recover_l1_action_signer(connection_id, &signature, true)?,
+            wallet.address()
+        );
+        // the same signature recovers to another address on testnet
+        assert_ne!(
+            recover_l1_action_signer(connection_id, &signature, false)?,
+            wallet.address()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    prelude::*,
+    signature::{agent::l1, create_signature},
+    Error,
+};
+use ethers::{
+    signers::{LocalWallet, Signer},
+    types::{transaction::eip712::Eip712, Signature, H160, H256},
+};
+use std::{fmt::Debug, future::Future};
+
+/// Signs Hyperliquid actions for a single address.
+///
+/// Only `address` and `sign_hash` are required, which is enough to keep the key outside the
+/// process, e.g. in a signing daemon or a KMS. Signers that need to see the structured data, such
+/// as hardware wallets, can override `sign_typed_data` too.
+pub trait HyperliquidSigner: Debug + Send + Sync {
+    fn address(&self) -> H160;
+
+    /// Signs a 32 byte EIP-712 digest.
+    fn sign_hash(&self, hash: H256) -> impl Future<Output = Result<Signature>> + Send;
+
+    /// Signs a user-signed action such as `UsdSend` or `Withdraw3`.
+    fn sign_typed_data<T: Eip712 + Sync>(
+        &self,
+        payload: &T,
+    ) -> impl Future<Output = Result<Signature>> + Send {
+        async move {
+            let encoded = payload
+                .encode_eip712()
+                .map_err(|e| Error::Eip712(e.to_string()))?;
+            self.sign_hash(H256::from(encoded)).await
+        }
+    }
+
+    /// Signs the connection id of an L1 action, wrapped in the phantom `Agent` struct.
+    fn sign_l1_action(
+        &self,
+        connection_id: H256,
+        is_mainnet: bool,
+    ) -> impl Future<Output = Result<Signature>> + Send {
+        async move {
+            let source = if is_mainnet { "a" } else { "b" }.to_string();
+            self.sign_typed_data(&l1::Agent {
+                source,
+                connection_id,
+            })
+            .await
+        }
+    }
+}
+
+impl HyperliquidSigner for LocalWallet {
+    fn address(&self) -> H160 {
+        Signer::address(self)
+    }
+
+    async fn sign_hash(&self, hash: H256) -> Result<Signature> {
+        create_signature::sign_hash(hash, self)
+    }
+
+    async fn sign_typed_data<T: Eip712 + Sync>(&self, payload: &T) -> Result<Signature> {
+        create_signature::sign_typed_data(payload, self)
+    }
+
+    async fn sign_l1_action(&self, connection_id: H256, is_mainnet: bool) -> Result<Signature> {
+        create_signature::sign_l1_action(self, connection_id, is_mainnet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UsdSend;
+
+    /// Stands in for a signer that only exposes raw digest signing.
+    #[derive(Debug)]
+    struct DigestSigner(LocalWallet);
+
+    impl HyperliquidSigner for DigestSigner {
+        fn address(&self) -> H160 {
+            Signer::address(&self.0)
+        }
+
+        async fn sign_hash(&self, hash: H256) -> Result<Signature> {
+            create_signature::sign_hash(hash, &self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_methods_match_local_wallet() -> Result<()> {
+        let wallet: LocalWallet =
+            "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+                .parse()
+                .map_err(|e: ethers::signers::WalletError| Error::Wallet(e.to_string()))?;
+        let signer = DigestSigner(wallet.clone());
+
+        let connection_id = H256::repeat_byte(7);
+        assert_eq!(
+            signer.sign_l1_action(connection_id, true).await?,
+            wallet.sign_l1_action(connection_id, true).await?
+        );
+
+        let usd_send = UsdSend {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            destination: "0x0D1d9635D0640821d15e323ac8AdADfA9c111414".to_string(),
+            amount: "1".to_string(),
+            time: 1690393044548,
+        };
+        assert_eq!(
+            signer.sign_typed_data(&usd_send).await?.to_string(),
+            "214d507bbdaebba52fa60928f904a8b2df73673e3baba6133d66fe846c7ef70451e82453a6d8db124e7ed6e60fa00d4b7c46e4d96cb2bd61fd81b6e8953cc9d21b"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{prelude::*, Error};
+use ethers::signers::LocalWallet;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Command line flag read by [`load_wallet_from_args`].
+pub const WALLET_FLAG: &str = "--wallet";
+/// Environment variable holding the password of a keystore given without one.
+pub const KEYSTORE_PASSWORD_ENV: &str = "HYPERLIQUID_KEYSTORE_PASSWORD";
+
+/// Where to load a private key from.
+///
+/// Parses from `env:<VAR>`, `file:<PATH>`, `keystore:<PATH>` or a bare hex private key.
+#[derive(Clone, PartialEq, Eq)]
+pub enum WalletSource {
+    /// Hex encoded private key, with or without `0x`.
+    PrivateKey(String),
+    /// Environment variable holding a hex encoded private key.
+    Env(String),
+    /// File holding a hex encoded private key. On unix it must not be accessible by group or
+    /// others.
+    KeyFile(PathBuf),
+    /// Encrypted JSON keystore. Without a password, it is read from [`KEYSTORE_PASSWORD_ENV`].
+    Keystore {
+        path: PathBuf,
+        password: Option<String>,
+    },
+}
+
+impl WalletSource {
+    pub fn load(&self) -> Result<LocalWallet> {
+        match self {
+            WalletSource::PrivateKey(key) => parse_private_key(key),
+            WalletSource::Env(var) => {
+                let key = std::env::var(var)
+                    .map_err(|e| Error::Wallet(format!("Could not read {var}: {e}")))?;
+                parse_private_key(&key)
+            }
+            WalletSource::KeyFile(path) => {
+                check_key_file_permissions(path)?;
+                let key = std::fs::read_to_string(path).map_err(|e| {
+                    Error::Wallet(format!("Could not read key file {}: {e}", path.display()))
+                })?;
+                parse_private_key(&key)
+            }
+            WalletSource::Keystore { path, password } => {
+                let password = match password {
+                    Some(password) => password.clone(),
+                    None => std::env::var(KEYSTORE_PASSWORD_ENV).map_err(|e| {
+                        Error::Wallet(format!("Could not read {KEYSTORE_PASSWORD_ENV}: {e}"))
+                    })?,
+                };
+                LocalWallet::decrypt_keystore(path, password).map_err(|e| {
+                    Error::Wallet(format!(
+                        "Could not decrypt keystore {}: {e}",
+                        path.display()
+                    ))
+                })
+            }
+        }
+    }
+
+    /// Returns the source given with `--wallet <SOURCE>` or `--wallet=<SOURCE>`, if any.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Option<WalletSource>> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == WALLET_FLAG {
+                let value = args
+                    .next()
+                    .ok_or_else(|| Error::Wallet(format!("{WALLET_FLAG} requires a value")))?;
+                return value.parse().map(Some);
+            }
+            if let Some(value) = arg
+                .strip_prefix(WALLET_FLAG)
+                .and_then(|rest| rest.strip_prefix('='))
+            {
+                return value.parse().map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl FromStr for WalletSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let source = match s.split_once(':') {
+            Some(("env", var)) => WalletSource::Env(var.to_string()),
+            Some(("file", path)) => WalletSource::KeyFile(path.into()),
+            Some(("keystore", path)) => WalletSource::Keystore {
+                path: path.into(),
+                password: None,
+            },
+            Some((kind, _)) => {
+                return Err(Error::Wallet(format!("Unknown wallet source {kind}")));
+            }
+            None => WalletSource::PrivateKey(s.to_string()),
+        };
+        Ok(source)
+    }
+}
+
+// keeps private keys and passwords out of logs
+impl fmt::Debug for WalletSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WalletSource::PrivateKey(_) => f.write_str("PrivateKey(..)"),
+            WalletSource::Env(var) => f.debug_tuple("Env").field(var).finish(),
+            WalletSource::KeyFile(path) => f.debug_tuple("KeyFile").field(path).finish(),
+            WalletSource::Keystore { path, .. } => {
+                f.debug_struct("Keystore").field("path", path).finish()
+            }
+        }
+    }
+}
+
+/// Loads the wallet given with `--wallet`, falling back to `default_key`.
+pub fn load_wallet_from_args(default_key: &str) -> Result<LocalWallet> {
+    WalletSource::from_args(std::env::args().skip(1))?
+        .unwrap_or_else(|| WalletSource::PrivateKey(default_key.to_string()))
+        .load()
+}
+
+fn parse_private_key(key: &str) -> Result<LocalWallet> {
+    let key = key.trim();
+    let key = key.strip_prefix("0x").unwrap_or(key);
+    key.parse()
+        .map_err(|e: ethers::signers::WalletError| Error::PrivateKeyParse(e.to_string()))
+}
+
+#[cfg(unix)]
+fn check_key_file_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = std::fs::metadata(path)
+        .map_err(|e| Error::Wallet(format!("Could not read key file {}: {e}", path.display())))?;
+    let mode = metadata.permissions().mode();
+    if mode & 0o077 != 0 {
+        return Err(Error::Wallet(format!(
+            "Key file {} has mode {:o}, expected it to be readable only by its owner (chmod 600)",
+            path.display(),
+            mode & 0o777
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_key_file_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::Signer;
+
+    const KEY: &str = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("hl-wallet-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn test_parse_sources() -> Result<()> {
+        let args = ["bin", "--wallet", "env:HL_KEY"].map(String::from);
+        assert_eq!(
+            WalletSource::from_args(args)?,
+            Some(WalletSource::Env("HL_KEY".to_string()))
+        );
+        let args = ["bin", "--wallet=keystore:/tmp/key.json"].map(String::from);
+        assert_eq!(
+            WalletSource::from_args(args)?,
+            Some(WalletSource::Keystore {
+                path: "/tmp/key.json".into(),
+                password: None
+            })
+        );
+        assert_eq!(WalletSource::from_args(["bin".to_string()])?, None);
+        assert!(WalletSource::from_args(["--wallet".to_string()]).is_err());
+        assert!("ledger:0".parse::<WalletSource>().is_err());
+
+        let wallet = WalletSource::PrivateKey(format!("0x{KEY}\n")).load()?;
+        assert_eq!(
+            wallet.address(),
+            KEY.parse::<LocalWallet>().unwrap().address()
+        );
+        assert!(matches!(
+            WalletSource::PrivateKey("abc".to_string()).load(),
+            Err(Error::PrivateKeyParse(_))
+        ));
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_key_file_permissions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = temp_path("key");
+        std::fs::write(&path, KEY).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let source = WalletSource::KeyFile(path.clone());
+        assert!(matches!(source.load(), Err(Error::Wallet(_))));
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        let loaded = source.load();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            loaded?.address(),
+            KEY.parse::<LocalWallet>().unwrap().address()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_keystore() -> Result<()> {
+        let dir = temp_path("keystore");
+        std::fs::create_dir_all(&dir).unwrap();
+        let (wallet, _) =
+            LocalWallet::new_keystore(&dir, &mut rand::thread_rng(), "hunter2", Some("key"))
+                .unwrap();
+        let path = dir.join("key");
+
+        let loaded = WalletSource::Keystore {
+            path: path.clone(),
+            password: Some("hunter2".to_string()),
+        }
+        .load();
+        let wrong_password = WalletSource::Keystore {
+            path,
+            password: Some("hunter3".to_string()),
+        }
+        .load();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded?.address(), wallet.address());
+        assert!(matches!(wrong_password, Err(Error::Wallet(_))));
+        Ok(())
+    }
+}
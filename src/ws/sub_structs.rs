@@ -1,3 +1,4 @@
+use crate::{Decimal, FillDirection, Side};
 use ethers::types::H160;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -5,9 +6,9 @@ use std::collections::HashMap;
 #[derive(Deserialize, Clone, Debug)]
 pub struct Trade {
     pub coin: String,
-    pub side: String,
-    pub px: String,
-    pub sz: String,
+    pub side: Side,
+    pub px: Decimal,
+    pub sz: Decimal,
     pub time: u64,
     pub hash: String,
     pub tid: u64,
@@ -15,8 +16,8 @@ pub struct Trade {
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct BookLevel {
-    pub px: String,
-    pub sz: String,
+    pub px: Decimal,
+    pub sz: Decimal,
     pub n: u64,
 }
 
@@ -36,18 +37,18 @@ pub struct AllMidsData {
 #[serde(rename_all = "camelCase")]
 pub struct TradeInfo {
     pub coin: String,
-    pub side: String,
-    pub px: String,
-    pub sz: String,
+    pub side: Side,
+    pub px: Decimal,
+    pub sz: Decimal,
     pub time: u64,
     pub hash: String,
-    pub start_position: String,
-    pub dir: String,
-    pub closed_pnl: String,
+    pub start_position: Decimal,
+    pub dir: FillDirection,
+    pub closed_pnl: Decimal,
     pub oid: u64,
     pub cloid: Option<String>,
     pub crossed: bool,
-    pub fee: String,
+    pub fee: Decimal,
     pub tid: u64,
 }
 
@@ -59,6 +60,66 @@ pub struct UserFillsData {
     pub fills: Vec<TradeInfo>,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapSliceFill {
+    pub fill: TradeInfo,
+    pub twap_id: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTwapSliceFillsData {
+    pub is_snapshot: Option<bool>,
+    pub user: H160,
+    pub twap_slice_fills: Vec<TwapSliceFill>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapState {
+    pub coin: String,
+    pub user: H160,
+    pub side: Side,
+    pub sz: Decimal,
+    pub executed_sz: Decimal,
+    pub executed_ntl: Decimal,
+    pub minutes: u64,
+    pub reduce_only: bool,
+    pub randomize: bool,
+    pub timestamp: u64,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TwapStatusKind {
+    Activated,
+    Terminated,
+    Finished,
+    Error,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TwapStatus {
+    pub status: TwapStatusKind,
+    pub description: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TwapHistory {
+    pub state: TwapState,
+    pub status: TwapStatus,
+    pub time: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserTwapHistoryData {
+    pub is_snapshot: Option<bool>,
+    pub user: H160,
+    pub history: Vec<TwapHistory>,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub enum UserData {
@@ -119,12 +180,12 @@ pub struct OrderUpdate {
 #[serde(rename_all = "camelCase")]
 pub struct BasicOrder {
     pub coin: String,
-    pub side: String,
-    pub limit_px: String,
-    pub sz: String,
+    pub side: Side,
+    pub limit_px: Decimal,
+    pub sz: Decimal,
     pub oid: u64,
     pub timestamp: u64,
-    pub orig_sz: String,
+    pub orig_sz: Decimal,
     pub cloid: Option<String>,
 }
 
@@ -245,6 +306,80 @@ pub struct VaultLeaderCommission {
     pub usdc: String,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDetails {
+    pub name: String,
+    pub vault_address: H160,
+    pub leader: H160,
+    pub description: String,
+    /// PnL and account value history per period ("day", "week", "month", "allTime", ...)
+    pub portfolio: Vec<(String, VaultPortfolio)>,
+    pub apr: Decimal,
+    /// State of the `user` passed to the query, if they follow the vault
+    pub follower_state: Option<VaultFollower>,
+    pub leader_fraction: Decimal,
+    pub leader_commission: Decimal,
+    pub followers: Vec<VaultFollower>,
+    pub max_distributable: Decimal,
+    pub max_withdrawable: Decimal,
+    pub is_closed: bool,
+    pub relationship: VaultRelationship,
+    pub allow_deposits: bool,
+    pub always_close_on_withdraw: bool,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultPortfolio {
+    /// `(timestamp, value)` pairs
+    pub account_value_history: Vec<(u64, Decimal)>,
+    pub pnl_history: Vec<(u64, Decimal)>,
+    pub vlm: Decimal,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultFollower {
+    pub user: H160,
+    pub vault_equity: Decimal,
+    pub pnl: Decimal,
+    pub all_time_pnl: Decimal,
+    pub days_following: u64,
+    pub vault_entry_time: u64,
+    pub lockup_until: u64,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VaultRelationshipType {
+    Normal,
+    Parent,
+    Child,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct VaultRelationship {
+    #[serde(rename = "type")]
+    pub relationship_type: VaultRelationshipType,
+    pub data: Option<VaultRelationshipData>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultRelationshipData {
+    #[serde(default)]
+    pub child_addresses: Vec<H160>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserVaultEquity {
+    pub vault_address: H160,
+    pub equity: Decimal,
+    pub locked_until_timestamp: u64,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountClassTransfer {
@@ -321,3 +456,58 @@ pub struct SpotAssetCtx {
     pub shared: SharedAssetCtx,
     pub circulating_supply: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_details_deserialization() {
+        let details: VaultDetails = serde_json::from_str(
+            r#"{
+                "name": "Test",
+                "vaultAddress": "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303",
+                "leader": "0x677d831aef5328190852e24f13c46cac05f984e7",
+                "description": "Example vault",
+                "portfolio": [
+                    ["day", {"accountValueHistory": [[1734397526634, "329265410.90790099"]], "pnlHistory": [[1734397526634, "0.0"]], "vlm": "0.0"}]
+                ],
+                "apr": 0.36387129259090006,
+                "followerState": null,
+                "leaderFraction": 0.0007904828725729887,
+                "leaderCommission": 0,
+                "followers": [{
+                    "user": "0x005844b2ffb2e122cf4244be7dbcb4f84924907c",
+                    "vaultEquity": "714491.71026243",
+                    "pnl": "3203.31592143",
+                    "allTimePnl": "79843.74476743",
+                    "daysFollowing": 388,
+                    "vaultEntryTime": 1711978703919,
+                    "lockupUntil": 1712410703919
+                }],
+                "maxDistributable": 94856870.164485,
+                "maxWithdrawable": 742557.680863,
+                "isClosed": false,
+                "relationship": {"type": "parent", "data": {"childAddresses": ["0x010461c14e146ac35fe42271bdc1134ee31c703a"]}},
+                "allowDeposits": true,
+                "alwaysCloseOnWithdraw": false
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(details.portfolio[0].0, "day");
+        assert_eq!(
+            details.portfolio[0].1.account_value_history[0].1,
+            "329265410.90790099".parse().unwrap()
+        );
+        assert_eq!(details.max_withdrawable, "742557.680863".parse().unwrap());
+        assert_eq!(details.leader_commission, Decimal::ZERO);
+        assert!(details.follower_state.is_none());
+        assert_eq!(details.followers[0].days_following, 388);
+        assert_eq!(
+            details.relationship.relationship_type,
+            VaultRelationshipType::Parent
+        );
+        assert_eq!(details.relationship.data.unwrap().child_addresses.len(), 1);
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     prelude::*,
     ws::message_types::{AllMids, Candle, L2Book, OrderUpdates, Trades, User},
     ActiveAssetCtx, Error, Notification, UserFills, UserFundings, UserNonFundingLedgerUpdates,
-    WebData2,
+    UserTwapHistory, UserTwapSliceFills, WebData2,
 };
 use futures_util::{stream::SplitSink, SinkExt, StreamExt};
 use log::{error, info, warn};
@@ -62,6 +62,8 @@ pub enum Subscription {
     UserFundings { user: H160 },
     UserNonFundingLedgerUpdates { user: H160 },
     ActiveAssetCtx { coin: String },
+    UserTwapSliceFills { user: H160 },
+    UserTwapHistory { user: H160 },
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -83,6 +85,8 @@ pub enum Message {
     Notification(Notification),
     WebData2(WebData2),
     ActiveAssetCtx(ActiveAssetCtx),
+    UserTwapSliceFills(UserTwapSliceFills),
+    UserTwapHistory(UserTwapHistory),
     Pong,
 }
 
@@ -267,6 +271,18 @@ impl WsManager {
                 })
                 .map_err(|e| Error::JsonParse(e.to_string()))
             }
+            Message::UserTwapSliceFills(twap_slice_fills) => {
+                serde_json::to_string(&Subscription::UserTwapSliceFills {
+                    user: twap_slice_fills.data.user,
+                })
+                .map_err(|e| Error::JsonParse(e.to_string()))
+            }
+            Message::UserTwapHistory(twap_history) => {
+                serde_json::to_string(&Subscription::UserTwapHistory {
+                    user: twap_history.data.user,
+                })
+                .map_err(|e| Error::JsonParse(e.to_string()))
+            }
             Message::SubscriptionResponse | Message::Pong => Ok(String::default()),
             Message::NoData => Ok("".to_string()),
             Message::HyperliquidError(err) => Ok(format!("hyperliquid error: {err:?}")),